@@ -0,0 +1,76 @@
+//! Compares the bulk `fill` APIs against filling a buffer one element at a time.
+//!
+//! Run with `cargo bench --bench fill`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use rng::Rng;
+
+const LEN: usize = 1 << 16;
+const ROUNDS: u32 = 200;
+
+fn bench<T: Clone + Default>(name: &str, f: impl Fn(&mut Rng, &mut [T])) -> Duration {
+    let rng = &mut Rng::from_seed(0);
+    let mut buf = vec![T::default(); LEN];
+
+    // Warm up
+    for _ in 0..ROUNDS / 10 {
+        f(rng, &mut buf);
+    }
+
+    let start = Instant::now();
+
+    for _ in 0..ROUNDS {
+        f(rng, &mut buf);
+        black_box(&buf);
+    }
+
+    let per_round = start.elapsed() / ROUNDS;
+    let per_elem = per_round.as_secs_f64() * 1e9 / LEN as f64;
+
+    println!("{name:<24} {per_round:>12.2?}/round {per_elem:>8.3} ns/elem");
+
+    per_round
+}
+
+fn compare<T: Clone + Default>(
+    name: &str,
+    per_element: impl Fn(&mut Rng, &mut [T]),
+    bulk: impl Fn(&mut Rng, &mut [T]),
+) {
+    let slow = bench(&format!("{name} (loop)"), per_element);
+    let fast = bench(&format!("{name} (fill)"), bulk);
+
+    println!(
+        "{:<24} {:.2}x\n",
+        "speedup",
+        slow.as_secs_f64() / fast.as_secs_f64()
+    );
+}
+
+fn main() {
+    compare::<u8>(
+        "u8",
+        |rng, buf| buf.iter_mut().for_each(|x| *x = rng.gen_value()),
+        |rng, buf| rng.fill(buf),
+    );
+
+    compare::<u64>(
+        "u64",
+        |rng, buf| buf.iter_mut().for_each(|x| *x = rng.gen_value()),
+        |rng, buf| rng.fill(buf),
+    );
+
+    compare::<f32>(
+        "f32",
+        |rng, buf| buf.iter_mut().for_each(|x| *x = rng.gen_value()),
+        |rng, buf| rng.fill(buf),
+    );
+
+    compare::<u32>(
+        "u32 in 1..=6",
+        |rng, buf| buf.iter_mut().for_each(|x| *x = rng.gen_range(1..=6)),
+        |rng, buf| rng.fill_range(buf, 1..=6),
+    );
+}
@@ -0,0 +1,128 @@
+use syn::{Attribute, Expr, ExprLit, Field, Lit, Variant};
+
+/// How a single field is generated, set with a `#[fuzz(..)]` attribute.
+pub(crate) enum FieldKind {
+    /// `RandomInstance::random_instance_with`
+    Random,
+    /// `#[fuzz(range = 0..=120)]`
+    Range(Expr),
+    /// `#[fuzz(len = 1..10)]`, the length of a collection or string
+    Len(Expr),
+    /// `#[fuzz(with = my_fn)]`, called as `my_fn(ctx)`
+    With(Expr),
+    /// `#[fuzz(default)]`, uses `Default::default()`
+    Default,
+    /// `#[fuzz(regex = "[a-z]+")]`, a string matching the pattern
+    Regex(Expr),
+    /// `#[fuzz(grammar = MY_GRAMMAR)]`, a sentence from the grammar's start rule
+    Grammar(Expr),
+}
+
+fn fuzz_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|a| a.path().is_ident("fuzz"))
+}
+
+pub(crate) fn parse_field_kind(field: &Field) -> syn::Result<FieldKind> {
+    let mut kind = FieldKind::Random;
+
+    for attr in fuzz_attrs(&field.attrs) {
+        attr.parse_nested_meta(|meta| {
+            if !matches!(kind, FieldKind::Random) {
+                return Err(meta.error("only one #[fuzz(..)] option is allowed per field"));
+            }
+
+            kind = if meta.path.is_ident("range") {
+                FieldKind::Range(meta.value()?.parse()?)
+            } else if meta.path.is_ident("len") {
+                FieldKind::Len(meta.value()?.parse()?)
+            } else if meta.path.is_ident("with") {
+                FieldKind::With(meta.value()?.parse()?)
+            } else if meta.path.is_ident("default") {
+                FieldKind::Default
+            } else if meta.path.is_ident("regex") {
+                FieldKind::Regex(meta.value()?.parse()?)
+            } else if meta.path.is_ident("grammar") {
+                FieldKind::Grammar(meta.value()?.parse()?)
+            } else {
+                return Err(
+                    meta.error("expected `range`, `len`, `with`, `default`, `regex` or `grammar`")
+                );
+            };
+
+            Ok(())
+        })?;
+    }
+
+    Ok(kind)
+}
+
+/// Reads `#[fuzz(weight = n)]` on a variant, defaulting to `1.0`.
+pub(crate) fn parse_weight(variant: &Variant) -> syn::Result<f64> {
+    let mut weight = None;
+
+    for attr in fuzz_attrs(&variant.attrs) {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("weight") {
+                return Err(meta.error("expected `weight`"));
+            }
+
+            if weight.is_some() {
+                return Err(meta.error("weight is already set"));
+            }
+
+            let expr: Expr = meta.value()?.parse()?;
+
+            let value = match &expr {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Int(lit), ..
+                }) => lit.base10_parse::<f64>()?,
+                Expr::Lit(ExprLit {
+                    lit: Lit::Float(lit),
+                    ..
+                }) => lit.base10_parse::<f64>()?,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        expr,
+                        "weight must be a number literal",
+                    ));
+                }
+            };
+
+            if !value.is_finite() || value < 0.0 {
+                return Err(syn::Error::new_spanned(
+                    expr,
+                    "weight must be a finite, non-negative number",
+                ));
+            }
+
+            weight = Some(value);
+
+            Ok(())
+        })?;
+    }
+
+    Ok(weight.unwrap_or(1.0))
+}
+
+/// Reads `#[fuzz(filter = my_fn)]` on the type, called as `my_fn(&value)`.
+pub(crate) fn parse_filter(attrs: &[Attribute]) -> syn::Result<Option<Expr>> {
+    let mut filter = None;
+
+    for attr in fuzz_attrs(attrs) {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("filter") {
+                return Err(meta.error("expected `filter`"));
+            }
+
+            if filter.is_some() {
+                return Err(meta.error("filter is already set"));
+            }
+
+            filter = Some(meta.value()?.parse()?);
+
+            Ok(())
+        })?;
+    }
+
+    Ok(filter)
+}
@@ -0,0 +1,262 @@
+use proc_macro::TokenStream;
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::{ToTokens, quote};
+use syn::{
+    Data, DataEnum, DeriveInput, Expr, Field, Fields, Generics, Ident, parse_macro_input,
+    parse_quote,
+};
+
+use crate::attrs::{FieldKind, parse_field_kind, parse_filter, parse_weight};
+
+/// How many instances a `#[fuzz(filter = ..)]` type generates before giving up.
+const MAX_FILTER_ATTEMPTS: usize = 1000;
+
+pub(super) fn derive_random_instance(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let generics = add_trait_bounds(input.generics.clone());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let mut body = match &input.data {
+        Data::Struct(data) => construct(quote!(Self), &data.fields)?,
+        Data::Enum(data) => expand_enum(name, data)?,
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "RandomInstance cannot be derived for unions",
+            ));
+        }
+    };
+
+    // Regenerate until the value passes the filter. Once fuzzer input has run out every
+    // attempt reads the same zeros, so there's no point in trying again
+    if let Some(filter) = parse_filter(&input.attrs)? {
+        let message =
+            format!("no generated `{name}` passed the filter in {MAX_FILTER_ATTEMPTS} attempts");
+        let out_of_input =
+            format!("ran out of fuzzer input before a generated `{name}` passed the filter");
+
+        body = quote! {
+            for _ in 0..#MAX_FILTER_ATTEMPTS {
+                let value: Self = { #body };
+
+                if #filter(&value) {
+                    return value;
+                }
+
+                if ctx.is_out_of_input() {
+                    panic!(#out_of_input);
+                }
+            }
+
+            panic!(#message)
+        };
+    }
+
+    Ok(quote! {
+        impl #impl_generics RandomInstance for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn random_instance_with(ctx: &mut GenContext<'_>) -> Self {
+                #body
+            }
+        }
+    })
+}
+
+/// Requires every type parameter to implement `RandomInstance`.
+fn add_trait_bounds(mut generics: Generics) -> Generics {
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(RandomInstance));
+    }
+
+    generics
+}
+
+fn expand_enum(name: &Ident, data: &DataEnum) -> syn::Result<TokenStream2> {
+    // Collect the selectable variants with their position in the enum and their weights
+    let mut selectable = Vec::new();
+
+    for (position, variant) in data.variants.iter().enumerate() {
+        let weight = parse_weight(variant)?;
+
+        if weight > 0.0 {
+            selectable.push((position, weight));
+        }
+    }
+
+    if selectable.is_empty() {
+        return Err(syn::Error::new_spanned(
+            name,
+            "RandomInstance needs at least one variant with a positive weight",
+        ));
+    }
+
+    // Generate match arms for each variant. Recursion goes through `Box`, `Rc`, `Arc` or a
+    // collection, which generate their contents one nesting level deeper
+    let arms = data
+        .variants
+        .iter()
+        .enumerate()
+        .map(|(position, variant)| {
+            let variant_name = &variant.ident;
+            let value = construct(quote!(Self::#variant_name), &variant.fields)?;
+
+            Ok(quote!(#position => #value))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let (recursive, leaves): (Vec<_>, Vec<_>) = selectable
+        .iter()
+        .partition(|&&(position, _)| is_recursive(name, &data.variants[position].fields));
+
+    let index = if recursive.is_empty() || leaves.is_empty() {
+        pick(&selectable)
+    } else {
+        // Recursive variants get less likely as the depth budget runs out, and are never
+        // picked once it's exhausted, so generation always terminates
+        let total = |variants: &[(usize, f64)]| variants.iter().map(|&(_, w)| w).sum::<f64>();
+        let recursive_share = total(&recursive) / total(&selectable);
+
+        let pick_recursive = pick(&recursive);
+        let pick_leaf = pick(&leaves);
+
+        quote! {{
+            let remaining = if ctx.is_exhausted() {
+                0.0
+            } else {
+                (ctx.max_depth() - ctx.depth()) as f64 / ctx.max_depth() as f64
+            };
+
+            if ctx.gen_bool(#recursive_share * remaining) {
+                #pick_recursive
+            } else {
+                #pick_leaf
+            }
+        }}
+    };
+
+    Ok(quote! {
+        match #index {
+            #(#arms,)*
+            _ => unreachable!(),
+        }
+    })
+}
+
+/// Picks one of the variant positions, uniformly or through a cumulative weight table.
+fn pick(variants: &[(usize, f64)]) -> TokenStream2 {
+    let count = variants.len();
+    let positions = variants.iter().map(|&(position, _)| position);
+
+    if variants.iter().all(|&(_, w)| w == variants[0].1) {
+        return quote! {{
+            const POSITIONS: [usize; #count] = [#(#positions),*];
+
+            POSITIONS[ctx.gen_range(0..#count)]
+        }};
+    }
+
+    let cumulative = variants
+        .iter()
+        .scan(0.0, |total, &(_, w)| {
+            *total += w;
+            Some(*total)
+        })
+        .collect::<Vec<f64>>();
+
+    quote! {{
+        const POSITIONS: [usize; #count] = [#(#positions),*];
+        const CUMULATIVE: [f64; #count] = [#(#cumulative),*];
+
+        let r = ctx.gen_range(0.0..CUMULATIVE[#count - 1]);
+
+        POSITIONS[CUMULATIVE.partition_point(|&c| c <= r).min(#count - 1)]
+    }}
+}
+
+/// Whether the fields can lead back to the type: any field's type mentions `name` or `Self`,
+/// like `Vec<Self>`, or a pointer that may hold a mutually recursive type, like `Box<Block>`.
+fn is_recursive(name: &Ident, fields: &Fields) -> bool {
+    fields
+        .iter()
+        .any(|f| mentions(f.ty.to_token_stream(), name))
+}
+
+fn mentions(tokens: TokenStream2, name: &Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => {
+            ident == *name || ["Self", "Box", "Rc", "Arc"].iter().any(|i| ident == i)
+        }
+        TokenTree::Group(group) => mentions(group.stream(), name),
+        _ => false,
+    })
+}
+
+/// Builds `path { a: .., b: .. }`, `path(.., ..)` or `path`, generating every field
+/// from the caller's `ctx`.
+fn construct(path: TokenStream2, fields: &Fields) -> syn::Result<TokenStream2> {
+    Ok(match fields {
+        Fields::Named(fields) => {
+            let values = fields
+                .named
+                .iter()
+                .map(|f| {
+                    let field_name = &f.ident;
+                    let value = field_value(f)?;
+
+                    Ok(quote!(#field_name: #value))
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            quote!(#path { #(#values,)* })
+        }
+        Fields::Unnamed(fields) => {
+            let values = fields
+                .unnamed
+                .iter()
+                .map(field_value)
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            quote!(#path(#(#values,)*))
+        }
+        Fields::Unit => path,
+    })
+}
+
+fn field_value(field: &Field) -> syn::Result<TokenStream2> {
+    let ty = &field.ty;
+
+    Ok(match parse_field_kind(field)? {
+        FieldKind::Random => quote!(<#ty as RandomInstance>::random_instance_with(ctx)),
+        FieldKind::Range(range) => quote!(ctx.gen_range::<#ty>(#range)),
+        FieldKind::Len(len) => quote! {
+            ctx.override_len(#len, |ctx| <#ty as RandomInstance>::random_instance_with(ctx))
+        },
+        FieldKind::With(func) => quote!(#func(ctx)),
+        FieldKind::Default => quote!(<#ty as ::core::default::Default>::default()),
+        FieldKind::Regex(pattern) => parsed_once(quote!(Regex), pattern),
+        FieldKind::Grammar(source) => parsed_once(quote!(Grammar), source),
+    })
+}
+
+/// Samples a `Regex` or `Grammar` that is parsed on first use and shared afterwards,
+/// converted into the field's type.
+fn parsed_once(ty: TokenStream2, source: Expr) -> TokenStream2 {
+    quote! {{
+        static PARSED: ::std::sync::OnceLock<#ty> = ::std::sync::OnceLock::new();
+
+        let parsed = PARSED.get_or_init(|| match #ty::new(#source) {
+            ::core::result::Result::Ok(parsed) => parsed,
+            ::core::result::Result::Err(e) => panic!("{}", e),
+        });
+
+        ::core::convert::Into::into(parsed.sample(ctx.rng()))
+    }}
+}
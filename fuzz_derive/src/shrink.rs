@@ -0,0 +1,179 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Field, Fields, Member, parse_macro_input, parse_quote};
+
+use crate::attrs::{FieldKind, parse_field_kind, parse_filter, parse_weight};
+
+pub(super) fn derive_shrink(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = input.ident;
+
+    let mut generics = input.generics;
+
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(Shrink));
+    }
+
+    // Candidates are built by cloning the value and replacing one field
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(Self: ::core::clone::Clone));
+
+    let mut body = match &input.data {
+        Data::Struct(data) => shrink_struct(&data.fields)?,
+        Data::Enum(data) => shrink_enum(data)?,
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "Shrink cannot be derived for unions",
+            ));
+        }
+    };
+
+    // Only keep candidates that `RandomInstance` could have generated
+    if let Some(filter) = parse_filter(&input.attrs)? {
+        body = quote! {
+            let candidates: Box<dyn Iterator<Item = Self> + '_> = { #body };
+
+            Box::new(candidates.filter(|value| #filter(value)))
+        };
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics Shrink for #name #ty_generics #where_clause {
+            fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+                #body
+            }
+        }
+    })
+}
+
+fn members(fields: &Fields) -> Vec<Member> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(idx, f)| match &f.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(idx.into()),
+        })
+        .collect()
+}
+
+/// Shrinks a field's value, keeping to its `#[fuzz(range = ..)]` or `#[fuzz(len = ..)]`.
+/// Fields set with `#[fuzz(with = ..)]`, `#[fuzz(default)]`, `#[fuzz(regex = ..)]` or
+/// `#[fuzz(grammar = ..)]` aren't shrunk.
+fn shrink_field(field: &Field, value: TokenStream2) -> syn::Result<Option<TokenStream2>> {
+    Ok(match parse_field_kind(field)? {
+        FieldKind::Random => Some(quote!(Shrink::shrink(#value))),
+        FieldKind::Range(range) => Some(quote! {
+            Shrink::shrink(#value)
+                .filter(|value| ::core::ops::RangeBounds::contains(&(#range), value))
+        }),
+        FieldKind::Len(len) => Some(quote! {
+            Shrink::shrink(#value)
+                .filter(|value| ::core::ops::RangeBounds::contains(&(#len), &value.len()))
+        }),
+        FieldKind::With(_) | FieldKind::Default | FieldKind::Regex(_) | FieldKind::Grammar(_) => {
+            None
+        }
+    })
+}
+
+/// Shrinks one field at a time, keeping the others unchanged.
+fn shrink_struct(fields: &Fields) -> syn::Result<TokenStream2> {
+    let mut parts = Vec::new();
+
+    for (field, member) in fields.iter().zip(members(fields)) {
+        let Some(shrunk) = shrink_field(field, quote!(&self.#member))? else {
+            continue;
+        };
+
+        parts.push(quote! {
+            Box::new(#shrunk.map(move |value| {
+                let mut shrunk = ::core::clone::Clone::clone(self);
+                shrunk.#member = value;
+                shrunk
+            }))
+        });
+    }
+
+    Ok(quote! {
+        let parts: Vec<Box<dyn Iterator<Item = Self> + '_>> = vec![#(#parts),*];
+
+        Box::new(parts.into_iter().flatten())
+    })
+}
+
+/// Tries the unit variants declared before the current one, then shrinks the current
+/// variant's fields one at a time.
+fn shrink_enum(data: &syn::DataEnum) -> syn::Result<TokenStream2> {
+    let mut arms = Vec::new();
+
+    for (idx, variant) in data.variants.iter().enumerate() {
+        let variant_name = &variant.ident;
+
+        // Variants with a zero weight are never generated, so don't shrink into them
+        let mut simpler_variants = Vec::new();
+
+        for v in data.variants.iter().take(idx) {
+            if matches!(v.fields, Fields::Unit) && parse_weight(v)? > 0.0 {
+                let unit_name = &v.ident;
+
+                simpler_variants.push(quote! {
+                    Box::new(::core::iter::once(Self::#unit_name))
+                });
+            }
+        }
+
+        let members = members(&variant.fields);
+        let bindings = (0..members.len())
+            .map(|i| format_ident!("__field_{}", i))
+            .collect::<Vec<_>>();
+
+        let mut field_parts = Vec::new();
+
+        for ((field, member), binding) in variant.fields.iter().zip(&members).zip(&bindings) {
+            let Some(shrunk) = shrink_field(field, quote!(#binding))? else {
+                continue;
+            };
+
+            field_parts.push(quote! {
+                Box::new(#shrunk.map(move |value| {
+                    let mut shrunk = ::core::clone::Clone::clone(self);
+
+                    if let Self::#variant_name { #member: field, .. } = &mut shrunk {
+                        *field = value;
+                    }
+
+                    shrunk
+                }))
+            });
+        }
+
+        arms.push(quote! {
+            Self::#variant_name { #(#members: #bindings),* } => {
+                let parts: Vec<Box<dyn Iterator<Item = Self> + '_>> =
+                    vec![#(#simpler_variants,)* #(#field_parts),*];
+
+                Box::new(parts.into_iter().flatten())
+            }
+        });
+    }
+
+    Ok(quote! {
+        match self {
+            #(#arms)*
+        }
+    })
+}
@@ -1,8 +1,14 @@
+mod random_value;
 mod random_variant;
 
 use proc_macro::TokenStream;
 
-#[proc_macro_derive(RandomVariant)]
+#[proc_macro_derive(RandomValue, attributes(rng))]
+pub fn derive_random_value(input: TokenStream) -> TokenStream {
+    random_value::derive_random_value(input)
+}
+
+#[proc_macro_derive(RandomVariant, attributes(weight, skip, rng))]
 pub fn derive_random_variant(input: TokenStream) -> TokenStream {
     random_variant::derive_random_variant(input)
 }
@@ -0,0 +1,160 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Expr, Field, Fields, Generics, parse_macro_input, parse_quote};
+
+pub(super) fn derive_random_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let generics = add_trait_bounds(input.generics.clone());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => construct(quote!(Self), &data.fields)?,
+        Data::Enum(data) => {
+            if data.variants.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "RandomValue cannot be derived for enums without variants",
+                ));
+            }
+
+            let variant_count = data.variants.len();
+            let arms = data
+                .variants
+                .iter()
+                .enumerate()
+                .map(|(idx, v)| {
+                    let variant_name = &v.ident;
+                    let value = construct(quote!(Self::#variant_name), &v.fields)?;
+
+                    Ok(quote! {
+                        #idx => #value,
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            quote! {
+                match rng.gen_range(0..#variant_count) {
+                    #(#arms)*
+                    _ => unreachable!(),
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "RandomValue cannot be derived for unions",
+            ));
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics RandomValue for #name #ty_generics #where_clause {
+            #[inline]
+            fn gen_value(rng: &mut Rng) -> Self {
+                #body
+            }
+        }
+    })
+}
+
+/// Requires every type parameter to implement `RandomValue`.
+pub(super) fn add_trait_bounds(mut generics: Generics) -> Generics {
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(RandomValue));
+    }
+
+    generics
+}
+
+/// Builds `path { a: .., b: .. }`, `path(.., ..)` or `path` from the fields.
+pub(super) fn construct(path: TokenStream2, fields: &Fields) -> syn::Result<TokenStream2> {
+    Ok(match fields {
+        Fields::Named(fields) => {
+            let values = fields
+                .named
+                .iter()
+                .map(|f| {
+                    let field_name = &f.ident;
+                    let value = field_value(f)?;
+
+                    Ok(quote!(#field_name: #value))
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            quote!(#path { #(#values,)* })
+        }
+        Fields::Unnamed(fields) => {
+            let values = fields
+                .unnamed
+                .iter()
+                .map(field_value)
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            quote!(#path(#(#values,)*))
+        }
+        Fields::Unit => path,
+    })
+}
+
+/// How a single field is generated, set with a `#[rng(..)]` attribute.
+enum FieldKind {
+    /// `RandomValue::gen_value`
+    Random,
+    /// `#[rng(range = 0..100)]`
+    Range(Expr),
+    /// `#[rng(value = 5)]`
+    Value(Expr),
+    /// `#[rng(with = my_fn)]`, called as `my_fn(rng)`
+    With(Expr),
+    /// `#[rng(skip)]`, uses `Default::default()`
+    Skip,
+}
+
+fn field_value(field: &Field) -> syn::Result<TokenStream2> {
+    let ty = &field.ty;
+
+    Ok(match parse_field_kind(field)? {
+        FieldKind::Random => quote!(<#ty as RandomValue>::gen_value(rng)),
+        FieldKind::Range(range) => quote!(rng.gen_range::<#ty>(#range)),
+        FieldKind::Value(value) => quote!(#value),
+        FieldKind::With(func) => quote!(#func(rng)),
+        FieldKind::Skip => quote!(<#ty as ::core::default::Default>::default()),
+    })
+}
+
+fn parse_field_kind(field: &Field) -> syn::Result<FieldKind> {
+    let mut kind = FieldKind::Random;
+
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("rng")) {
+        attr.parse_nested_meta(|meta| {
+            if !matches!(kind, FieldKind::Random) {
+                return Err(meta.error("only one #[rng(..)] option is allowed per field"));
+            }
+
+            kind = if meta.path.is_ident("range") {
+                FieldKind::Range(meta.value()?.parse()?)
+            } else if meta.path.is_ident("value") {
+                FieldKind::Value(meta.value()?.parse()?)
+            } else if meta.path.is_ident("with") {
+                FieldKind::With(meta.value()?.parse()?)
+            } else if meta.path.is_ident("skip") {
+                FieldKind::Skip
+            } else {
+                return Err(meta.error("expected `range`, `value`, `with` or `skip`"));
+            };
+
+            Ok(())
+        })?;
+    }
+
+    Ok(kind)
+}
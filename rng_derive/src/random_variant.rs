@@ -1,55 +1,229 @@
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
-use syn::{Data, DeriveInput, Fields, parse_macro_input};
+use syn::{
+    Attribute, Data, DeriveInput, Expr, ExprLit, Fields, Lit, Meta, Variant, parse_macro_input,
+};
+
+use crate::random_value::{add_trait_bounds, construct};
 
 pub(super) fn derive_random_variant(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
-    if let Data::Enum(enum_data) = input.data {
-        let enum_name = input.ident;
-        let variant_count = enum_data.variants.len();
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(enum_data) = input.data else {
+        return Err(syn::Error::new_spanned(
+            input.ident,
+            "RandomVariant must only be derived on enums",
+        ));
+    };
 
-        // Check if all variants are unit variants
-        if !enum_data
-            .variants
-            .iter()
-            .all(|v| matches!(v.fields, Fields::Unit))
-        {
-            panic!("RandomVariant does not work on non-unit variants");
+    let enum_name = input.ident;
+    let generics = add_trait_bounds(input.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let total_count = enum_data.variants.len();
+
+    // Collect the selectable variants with their position in the enum and their weights
+    let mut selectable = Vec::new();
+
+    for (position, v) in enum_data.variants.iter().enumerate() {
+        if has_skip(&v.attrs) {
+            continue;
         }
 
-        // Generate match arms
-        let mut match_arms = enum_data
-            .variants
+        let weight = parse_weight(v)?;
+
+        if weight > 0.0 {
+            selectable.push((position, v, weight));
+        }
+    }
+
+    if selectable.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &enum_name,
+            "RandomVariant needs at least one variant that isn't skipped and has a positive weight",
+        ));
+    }
+
+    let variant_count = selectable.len();
+    let uniform = selectable.iter().all(|&(_, _, w)| w == selectable[0].2);
+
+    // Generate match arms, building each variant's fields from `RandomValue`
+    let match_arms = selectable
+        .iter()
+        .enumerate()
+        .map(|(idx, (_, v, _))| {
+            let variant_name = &v.ident;
+            let value = construct(quote!(Self::#variant_name), &v.fields)?;
+
+            Ok(quote! {
+                #idx => #value,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let index = if uniform {
+        quote! {
+            rng.gen_range(0..#variant_count)
+        }
+    } else {
+        // Weighted selection through a cumulative table built at compile time
+        let cumulative = selectable
             .iter()
-            .enumerate()
-            .map(|(idx, v)| {
-                let variant_name = &v.ident;
+            .scan(0.0, |total, &(_, _, w)| {
+                *total += w;
+                Some(*total)
+            })
+            .collect::<Vec<f64>>();
+
+        quote! {{
+            const CUMULATIVE: [f64; #variant_count] = [#(#cumulative),*];
+
+            let r = rng.gen_value::<f64>() * CUMULATIVE[#variant_count - 1];
 
-                quote! {
-                    #idx => Self::#variant_name,
+            CUMULATIVE.partition_point(|&c| c <= r).min(#variant_count - 1)
+        }}
+    };
+
+    // Maps a value to its variant's position in the enum, ignoring any fields
+    let position_arms = enum_data.variants.iter().enumerate().map(|(position, v)| {
+        let variant_name = &v.ident;
+
+        quote! {
+            Self::#variant_name { .. } => #position,
+        }
+    });
+
+    let positions = selectable.iter().map(|&(position, _, _)| position);
+    let weights = selectable.iter().map(|&(_, _, w)| w);
+
+    // `all_variants` can only be built when no variant carries data
+    let all_variants = enum_data
+        .variants
+        .iter()
+        .all(|v| matches!(v.fields, Fields::Unit))
+        .then(|| {
+            let variant_names = enum_data.variants.iter().map(|v| &v.ident);
+
+            quote! {
+                /// Returns every variant of the enum, in declaration order.
+                pub fn all_variants() -> [Self; #total_count] {
+                    [#(Self::#variant_names),*]
                 }
-            })
-            .collect::<Vec<_>>();
+            }
+        });
+
+    let expanded = quote! {
+        impl #impl_generics RandomVariant for #enum_name #ty_generics #where_clause {
+            fn random_variant(rng: &mut Rng) -> Self {
+                match #index {
+                    #(#match_arms)*
+                    _ => unreachable!(),
+                }
+            }
 
-        match_arms.push(quote! {_ => panic!(),});
+            fn random_variant_except(rng: &mut Rng, except: &[Self]) -> Self {
+                const POSITIONS: [usize; #variant_count] = [#(#positions),*];
 
-        let match_arms = match_arms.into_iter();
+                let position_of = |v: &Self| -> usize {
+                    match v {
+                        #(#position_arms)*
+                    }
+                };
+
+                let mut weights: [f64; #variant_count] = [#(#weights),*];
 
-        let expanded = quote! {
-            impl RandomVariant for #enum_name {
-                fn random_variant(rng: &mut Rng) -> Self {
-                    match rng.gen_range(0..#variant_count) {
-                        #(#match_arms)*
+                for (weight, position) in weights.iter_mut().zip(POSITIONS) {
+                    if except.iter().any(|e| position_of(e) == position) {
+                        *weight = 0.0;
                     }
                 }
+
+                let total = weights.iter().sum::<f64>();
+
+                assert!(total > 0.0, "every selectable variant is excluded");
+
+                let mut r = rng.gen_value::<f64>() * total;
+                let mut idx = weights.iter().rposition(|&w| w > 0.0).unwrap();
+
+                for (i, &weight) in weights.iter().enumerate() {
+                    if weight > 0.0 && r < weight {
+                        idx = i;
+                        break;
+                    }
+
+                    r -= weight;
+                }
+
+                match idx {
+                    #(#match_arms)*
+                    _ => unreachable!(),
+                }
             }
-        };
+        }
 
-        // panic!("{}", expanded);
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            /// The number of variants in the enum, including skipped ones.
+            pub const VARIANT_COUNT: usize = #total_count;
 
-        return TokenStream::from(expanded);
-    } else {
-        panic!("RandomVariant must only be derived on enums");
+            #all_variants
+        }
+    };
+
+    Ok(expanded)
+}
+
+fn has_skip(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .any(|a| matches!(&a.meta, Meta::Path(path) if path.is_ident("skip")))
+}
+
+/// Reads `#[weight(10)]` or `#[weight = 0.5]`, defaulting to `1.0`.
+fn parse_weight(variant: &Variant) -> syn::Result<f64> {
+    let Some(attr) = variant.attrs.iter().find(|a| a.path().is_ident("weight")) else {
+        return Ok(1.0);
+    };
+
+    let expr: Expr = match &attr.meta {
+        Meta::List(list) => list.parse_args()?,
+        Meta::NameValue(nv) => nv.value.clone(),
+        Meta::Path(_) => {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "expected `#[weight(n)]` or `#[weight = n]`",
+            ));
+        }
+    };
+
+    let weight = match &expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => lit.base10_parse::<f64>()?,
+        Expr::Lit(ExprLit {
+            lit: Lit::Float(lit),
+            ..
+        }) => lit.base10_parse::<f64>()?,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                expr,
+                "weight must be a number literal",
+            ));
+        }
+    };
+
+    if !weight.is_finite() || weight < 0.0 {
+        return Err(syn::Error::new_spanned(
+            expr,
+            "weight must be a finite, non-negative number",
+        ));
     }
+
+    Ok(weight)
 }
@@ -2,8 +2,45 @@ use crate::*;
 
 use rng_derive::RandomVariant;
 
+/// Picks random variants of an enum. Derive it with `#[derive(RandomVariant)]`.
+///
+/// Variants can be weighted with `#[weight(n)]` and excluded with `#[skip]`. Fields of
+/// data-carrying variants are generated with `RandomValue`, and accept the same
+/// `#[rng(..)]` attributes as `#[derive(RandomValue)]`, e.g. `#[rng(skip)]` to use
+/// `Default`. The derive also adds an inherent `VARIANT_COUNT` constant, and enums
+/// without data get an inherent `all_variants()` function.
 pub trait RandomVariant {
     fn random_variant(rng: &mut Rng) -> Self;
+
+    /// Returns a random variant that is none of the variants in `except`. Only the
+    /// variants are compared, not their fields.
+    ///
+    /// The default implementation redraws `random_variant` until it returns a variant
+    /// that is not excluded. The derive overrides it to draw only once.
+    ///
+    /// # Panics
+    /// If every selectable variant is excluded. The default implementation detects this
+    /// by giving up after a large number of draws.
+    fn random_variant_except(rng: &mut Rng, except: &[Self]) -> Self
+    where
+        Self: Sized,
+    {
+        const MAX_ATTEMPTS: usize = 10_000;
+
+        for _ in 0..MAX_ATTEMPTS {
+            let variant = Self::random_variant(rng);
+            let discriminant = core::mem::discriminant(&variant);
+
+            if except
+                .iter()
+                .all(|e| core::mem::discriminant(e) != discriminant)
+            {
+                return variant;
+            }
+        }
+
+        panic!("every selectable variant is excluded");
+    }
 }
 
 #[derive(Debug, RandomVariant)]
@@ -21,3 +58,131 @@ fn test_random_variant() {
         dbg!(TestEnum::random_variant(rng));
     }
 }
+
+#[derive(Debug, PartialEq, RandomVariant)]
+enum Rarity {
+    #[weight(90)]
+    Common,
+    #[weight = 9.5]
+    Rare,
+    #[weight(0.5)]
+    Legendary,
+    #[skip]
+    Unknown,
+}
+
+#[test]
+fn test_random_variant_weighted() {
+    let rng = &mut Rng::from_seed(0);
+
+    let mut counts = [0; 4];
+
+    for _ in 0..100_000 {
+        counts[Rarity::random_variant(rng) as usize] += 1;
+    }
+
+    assert!((89_000..91_000).contains(&counts[0]));
+    assert!((9_000..10_000).contains(&counts[1]));
+    assert!((300..700).contains(&counts[2]));
+    assert_eq!(counts[3], 0);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, RandomVariant)]
+enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+#[derive(Debug, RandomVariant)]
+enum Event {
+    Idle,
+    Move(Direction, #[rng(range = 1..=3)] u8),
+    Say {
+        #[rng(skip)]
+        text: String,
+        loud: bool,
+    },
+    #[skip]
+    Quit(u32),
+}
+
+impl RandomValue for Direction {
+    fn gen_value(rng: &mut Rng) -> Self {
+        Direction::random_variant(rng)
+    }
+}
+
+#[test]
+fn test_random_variant_data() {
+    let rng = &mut Rng::from_seed(0);
+
+    assert_eq!(Direction::VARIANT_COUNT, 4);
+    assert_eq!(Event::VARIANT_COUNT, 4);
+    assert_eq!(
+        Direction::all_variants(),
+        [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West
+        ]
+    );
+
+    for _ in 0..1000 {
+        let current = Direction::random_variant(rng);
+        assert_ne!(Direction::random_variant_except(rng, &[current]), current);
+
+        match Event::random_variant(rng) {
+            Event::Move(_, steps) => assert!((1..=3).contains(&steps)),
+            Event::Say { text, .. } => assert!(text.is_empty()),
+            Event::Idle => {}
+            Event::Quit(_) => panic!("skipped variant was picked"),
+        }
+
+        assert!(matches!(
+            Event::random_variant_except(rng, &[Event::Idle, Event::Move(Direction::North, 1)]),
+            Event::Say { .. }
+        ));
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Coin {
+    Heads,
+    Tails,
+}
+
+// Written by hand, so it relies on the default `random_variant_except`
+impl RandomVariant for Coin {
+    fn random_variant(rng: &mut Rng) -> Self {
+        if rng.gen_bool(0.5) {
+            Coin::Heads
+        } else {
+            Coin::Tails
+        }
+    }
+}
+
+#[test]
+fn test_random_variant_except_default() {
+    let rng = &mut Rng::from_seed(0);
+
+    for _ in 0..100 {
+        assert_eq!(
+            Coin::random_variant_except(rng, &[Coin::Heads]),
+            Coin::Tails
+        );
+        assert_eq!(
+            Coin::random_variant_except(rng, &[Coin::Tails]),
+            Coin::Heads
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "every selectable variant is excluded")]
+fn test_random_variant_except_default_all_excluded() {
+    Coin::random_variant_except(&mut Rng::from_seed(0), &[Coin::Heads, Coin::Tails]);
+}
@@ -0,0 +1,88 @@
+use crate::{RandomRange, RandomValue, Rng};
+
+use core::ops::RangeBounds;
+
+impl Rng {
+    /// Fills `dest` with random bytes.
+    #[inline]
+    pub fn fill_bytes(&mut self, dest: &mut [u8]) {
+        const LANES: usize = 4;
+
+        let mut chunks = dest.chunks_exact_mut(LANES * 8);
+
+        for chunk in &mut chunks {
+            let lanes = self.next_u64_lanes::<LANES>();
+
+            for (bytes, lane) in chunk.chunks_exact_mut(8).zip(lanes) {
+                bytes.copy_from_slice(&lane.to_le_bytes());
+            }
+        }
+
+        for bytes in chunks.into_remainder().chunks_mut(8) {
+            let len = bytes.len();
+
+            bytes.copy_from_slice(&self.next_u64().to_le_bytes()[..len]);
+        }
+    }
+
+    /// Fills `dest` with random values.
+    ///
+    /// Equivalent to calling `gen_value` for each element. For raw bytes,
+    /// [`fill_bytes`](Self::fill_bytes) is faster since it uses all 8 bytes of each draw.
+    #[inline]
+    pub fn fill<T: RandomValue>(&mut self, dest: &mut [T]) {
+        T::fill(self, dest);
+    }
+
+    /// Fills `dest` with random values in `range`.
+    ///
+    /// Equivalent to calling `gen_range` for each element.
+    ///
+    /// # Panics
+    /// If the range is empty.
+    #[inline]
+    pub fn fill_range<T: RandomRange>(&mut self, dest: &mut [T], range: impl RangeBounds<T>) {
+        T::fill_range(self, dest, range);
+    }
+}
+
+#[test]
+fn test_fill() {
+    // Batched paths must match the per-element ones exactly, including the remainder
+    for len in [0, 1, 7, 8, 9, 31, 100] {
+        let mut filled = vec![0u32; len];
+        Rng::from_seed(3).fill(&mut filled);
+
+        let rng = &mut Rng::from_seed(3);
+        let expected: Vec<u32> = (0..len).map(|_| rng.gen_value()).collect();
+        assert_eq!(filled, expected);
+
+        let mut filled = vec![0.0f64; len];
+        Rng::from_seed(3).fill(&mut filled);
+
+        let rng = &mut Rng::from_seed(3);
+        let expected: Vec<f64> = (0..len).map(|_| rng.gen_value()).collect();
+        assert_eq!(filled, expected);
+
+        let mut filled = vec![0i16; len];
+        Rng::from_seed(3).fill_range(&mut filled, -5..=5);
+
+        let rng = &mut Rng::from_seed(3);
+        let expected: Vec<i16> = (0..len).map(|_| rng.gen_range(-5..=5)).collect();
+        assert_eq!(filled, expected);
+
+        let mut filled = vec![0.0f32; len];
+        Rng::from_seed(3).fill_range(&mut filled, 1.0..2.0);
+
+        let rng = &mut Rng::from_seed(3);
+        let expected: Vec<f32> = (0..len).map(|_| rng.gen_range(1.0..2.0)).collect();
+        assert_eq!(filled, expected);
+    }
+
+    let mut bytes = [0u8; 37];
+    Rng::from_seed(3).fill_bytes(&mut bytes);
+
+    let rng = &mut Rng::from_seed(3);
+    let expected: Vec<u8> = (0..5).flat_map(|_| rng.next_u64().to_le_bytes()).collect();
+    assert_eq!(bytes[..], expected[..37]);
+}
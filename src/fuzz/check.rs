@@ -0,0 +1,423 @@
+use crate::{DEFAULT_SIZE, GenContext, RandomInstance, Rng, Shrink, minimize};
+
+use core::cell::Cell;
+use core::fmt::Debug;
+use std::fs;
+use std::io::Write;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+
+/// Environment variable that replays a run from the given seed.
+pub const SEED_ENV: &str = "RNG_SEED";
+
+/// Environment variable that overrides how many cases are generated.
+pub const CASES_ENV: &str = "RNG_CASES";
+
+const DEFAULT_CASES: usize = 256;
+
+/// Directory, relative to the crate root, where `property_test!` stores failing cases.
+pub const REGRESSIONS_DIR: &str = "fuzz-regressions";
+
+/// The result of running a property on one input.
+///
+/// Implemented for `bool` (`false` fails), `()` (only panics fail) and `Result<(), E>`.
+pub trait TestOutcome {
+    fn into_result(self) -> Result<(), String>;
+}
+
+impl TestOutcome for bool {
+    #[inline]
+    fn into_result(self) -> Result<(), String> {
+        if self {
+            Ok(())
+        } else {
+            Err("property returned false".to_string())
+        }
+    }
+}
+
+impl TestOutcome for () {
+    #[inline]
+    fn into_result(self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl<E: Debug> TestOutcome for Result<(), E> {
+    #[inline]
+    fn into_result(self) -> Result<(), String> {
+        self.map_err(|e| format!("property returned an error: {e:?}"))
+    }
+}
+
+/// A property-test runner.
+///
+/// Generates inputs with `RandomInstance` from a recorded seed, and on failure shrinks the
+/// input and panics with the seed needed to replay the run. The generation size grows from `0`
+/// to `max_size` over the run, so early cases are small. The `RNG_SEED` and `RNG_CASES`
+/// environment variables override the configured seed and number of cases.
+///
+/// With [`with_regressions`](Self::with_regressions), failing cases are saved to a file and
+/// replayed before any new cases on later runs.
+#[derive(Clone, Debug)]
+pub struct Check {
+    cases: usize,
+    seed: Option<u64>,
+    max_size: usize,
+    regressions: Option<PathBuf>,
+}
+
+impl Default for Check {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Check {
+    /// Creates a runner that checks 256 cases from a random seed.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            cases: DEFAULT_CASES,
+            seed: None,
+            max_size: DEFAULT_SIZE,
+            regressions: None,
+        }
+    }
+
+    /// Sets the number of generated cases.
+    #[inline]
+    #[must_use]
+    pub fn with_cases(mut self, cases: usize) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// Sets a fixed seed instead of a random one.
+    #[inline]
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the size reached by the last case, see [`GenContext::with_size`].
+    #[inline]
+    #[must_use]
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Saves failing cases to `dir/name` and replays them before any new cases.
+    ///
+    /// Each line of the file holds the seed and size of one case, followed by the Debug
+    /// output of its minimal input as a comment. `::` in `name` is replaced with `.` so
+    /// module paths make valid file names.
+    #[inline]
+    #[must_use]
+    pub fn with_regressions(mut self, dir: impl AsRef<Path>, name: &str) -> Self {
+        self.regressions = Some(dir.as_ref().join(name.replace("::", ".")));
+        self
+    }
+
+    /// Checks `property` against generated inputs, after replaying any saved regressions.
+    ///
+    /// # Panics
+    /// If the property fails or panics for any input, with a report of the seed, the
+    /// original input and the shrunk input.
+    pub fn run<T, R>(&self, property: impl Fn(T) -> R)
+    where
+        T: RandomInstance + Shrink + Clone + Debug,
+        R: TestOutcome,
+    {
+        for (case_seed, size) in self.read_regressions() {
+            self.run_case(&property, case_seed, size, || {
+                format!("saved regression with seed {case_seed} failed")
+            });
+        }
+
+        let seed = env_var(SEED_ENV)
+            .or(self.seed)
+            .unwrap_or_else(|| Rng::new().gen_value());
+        let cases = env_var(CASES_ENV).unwrap_or(self.cases as u64);
+
+        let rng = &mut Rng::from_seed(seed);
+
+        for case in 0..cases {
+            let case_seed = rng.gen_value();
+            let size = (self.max_size as u64 * (case + 1) / cases) as usize;
+
+            self.run_case(&property, case_seed, size, || {
+                format!(
+                    "property failed on case {case} of {cases}\n\
+                     \x20 seed: {seed} (replay with {SEED_ENV}={seed})"
+                )
+            });
+        }
+    }
+
+    /// Generates one input from `case_seed` and checks it, shrinking and saving it on failure.
+    fn run_case<T, R>(
+        &self,
+        property: &impl Fn(T) -> R,
+        case_seed: u64,
+        size: usize,
+        describe: impl FnOnce() -> String,
+    ) where
+        T: RandomInstance + Shrink + Clone + Debug,
+        R: TestOutcome,
+    {
+        let rng = &mut Rng::from_seed(case_seed);
+        let input = T::random_instance_with(&mut GenContext::new(rng).with_size(size));
+
+        let Err(error) = check_input(property, input.clone()) else {
+            return;
+        };
+
+        // Panics while shrinking are expected, so keep them from flooding the output
+        let minimal = silence_panics(|| {
+            minimize(input.clone(), |candidate| {
+                check_input(property, candidate.clone()).is_err()
+            })
+        });
+        let minimal_error = check_input(property, minimal.clone())
+            .err()
+            .unwrap_or(error);
+
+        self.save_regression(case_seed, size, &minimal);
+
+        panic!(
+            "{}\n\
+             \x20 case seed: {case_seed}, size: {size}\n\
+             \x20 input: {input:?}\n\
+             \x20 minimal input: {minimal:?}\n\
+             \x20 error: {minimal_error}",
+            describe()
+        );
+    }
+
+    /// Reads the `(seed, size)` of every saved case.
+    ///
+    /// # Panics
+    /// If the file exists but can't be read or has a malformed line.
+    fn read_regressions(&self) -> Vec<(u64, usize)> {
+        let Some(path) = &self.regressions else {
+            return Vec::new();
+        };
+
+        let Ok(contents) = fs::read_to_string(path) else {
+            return Vec::new();
+        };
+
+        contents
+            .lines()
+            .map(|line| line.split('#').next().unwrap().trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let mut parts = line.split_whitespace().map(str::parse);
+
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some(Ok(seed)), Some(Ok(size)), None) => (seed, size as usize),
+                    _ => panic!(
+                        "malformed line in {}, expected `<seed> <size>`: {line:?}",
+                        path.display()
+                    ),
+                }
+            })
+            .collect()
+    }
+
+    /// Appends a failing case, unless it's already saved. Failing to write only warns, so
+    /// the property's failure is still what gets reported.
+    fn save_regression(&self, case_seed: u64, size: usize, minimal: &impl Debug) {
+        let Some(path) = &self.regressions else {
+            return;
+        };
+
+        if self.read_regressions().contains(&(case_seed, size)) {
+            return;
+        }
+
+        let result = (|| {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+
+            let mut file = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)?;
+
+            writeln!(file, "{case_seed} {size} # minimal input: {minimal:?}")
+        })();
+
+        if let Err(error) = result {
+            eprintln!(
+                "warning: failed to save regression to {}: {error}",
+                path.display()
+            );
+        }
+    }
+}
+
+/// Checks `property` against 256 generated inputs. See [`Check`] for configuration.
+#[inline]
+pub fn check<T, R>(property: impl Fn(T) -> R)
+where
+    T: RandomInstance + Shrink + Clone + Debug,
+    R: TestOutcome,
+{
+    Check::new().run(property);
+}
+
+/// Defines `#[test]` functions whose arguments are generated with `RandomInstance`,
+/// checked with [`Check`].
+///
+/// Failing cases are saved under [`REGRESSIONS_DIR`] in the crate root, in a file named after
+/// the test, and replayed first on later runs. Commit the files to keep the regressions.
+///
+/// The body can evaluate to `bool`, `()` or `Result<(), E>`.
+///
+/// ```ignore
+/// property_test! {
+///     fn reverse_twice(v: Vec<u8>) {
+///         let mut r = v.clone();
+///         r.reverse();
+///         r.reverse();
+///         r == v
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! property_test {
+    (
+        $(#[$meta:meta])*
+        fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $body:block
+        $($rest:tt)*
+    ) => {
+        $(#[$meta])*
+        #[test]
+        fn $name() {
+            $crate::Check::new()
+                .with_regressions(
+                    ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join($crate::REGRESSIONS_DIR),
+                    concat!(module_path!(), "::", stringify!($name)),
+                )
+                .run(|($($arg,)*): ($($ty,)*)| $body);
+        }
+
+        $crate::property_test!($($rest)*);
+    };
+    () => {};
+}
+
+fn env_var(name: &str) -> Option<u64> {
+    let value = std::env::var(name).ok()?;
+
+    Some(
+        value
+            .trim()
+            .parse()
+            .unwrap_or_else(|_| panic!("{name} must be an unsigned integer, got {value:?}")),
+    )
+}
+
+/// Runs the property on one input, treating panics as failures.
+fn check_input<T, R: TestOutcome>(property: &impl Fn(T) -> R, input: T) -> Result<(), String> {
+    match catch_unwind(AssertUnwindSafe(|| property(input))) {
+        Ok(outcome) => outcome.into_result(),
+        Err(payload) => Err(payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .map_or_else(
+                || "property panicked".to_string(),
+                |msg| format!("property panicked: {msg}"),
+            )),
+    }
+}
+
+thread_local! {
+    static PANICS_SILENCED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` without printing panic messages from the current thread.
+fn silence_panics<R>(f: impl FnOnce() -> R) -> R {
+    static INSTALL_HOOK: Once = Once::new();
+
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = std::panic::take_hook();
+
+        std::panic::set_hook(Box::new(move |info| {
+            if !PANICS_SILENCED.with(Cell::get) {
+                default_hook(info);
+            }
+        }));
+    });
+
+    PANICS_SILENCED.with(|s| s.set(true));
+    let result = f();
+    PANICS_SILENCED.with(|s| s.set(false));
+
+    result
+}
+
+#[cfg(test)]
+property_test! {
+    fn test_property_reverse(v: Vec<u8>) {
+        let mut r = v.clone();
+        r.reverse();
+        r.reverse();
+        r == v
+    }
+
+    fn test_property_sum_commutes(a: u32, b: u32) {
+        assert_eq!(a.wrapping_add(b), b.wrapping_add(a));
+    }
+}
+
+#[test]
+fn test_check_reports_minimal_input() {
+    let result = catch_unwind(|| {
+        Check::new()
+            .with_seed(1)
+            .run(|v: Vec<u32>| v.iter().all(|&x| x < 100));
+    });
+
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+
+    assert!(message.contains("minimal input: [100]"), "{message}");
+    assert!(message.contains("RNG_SEED="), "{message}");
+}
+
+#[test]
+fn test_check_regressions() {
+    let dir = std::env::temp_dir().join(format!("rng-regressions-{}", std::process::id()));
+    let check = Check::new()
+        .with_seed(3)
+        .with_regressions(&dir, "tests::failing");
+    let path = dir.join("tests.failing");
+
+    let _ = fs::remove_dir_all(&dir);
+
+    let fails = |v: Vec<u32>| v.len() < 3;
+
+    assert!(catch_unwind(|| check.run(fails)).is_err());
+
+    let saved = fs::read_to_string(&path).unwrap();
+    assert_eq!(saved.lines().count(), 1, "{saved}");
+    assert!(saved.contains("# minimal input: [0, 0, 0]"), "{saved}");
+
+    // The saved case is replayed, and fails again without generating new cases
+    let result = catch_unwind(|| check.clone().with_cases(0).run(fails));
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.starts_with("saved regression"), "{message}");
+    assert_eq!(fs::read_to_string(&path).unwrap(), saved);
+
+    check.with_cases(0).run(|_: Vec<u32>| true);
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,373 @@
+use crate::{RandomRange, Rng};
+
+use core::ops::RangeBounds;
+
+/// Default size of a [`GenContext`], the largest length a top-level collection can have.
+pub const DEFAULT_SIZE: usize = 64;
+
+/// Default number of collection levels that can be nested before they are generated empty.
+pub const DEFAULT_MAX_DEPTH: usize = 6;
+
+/// Default probability that a number is generated as a boundary or special value.
+pub const DEFAULT_EDGE_CASE_PROBABILITY: f64 = 0.2;
+
+/// State threaded through [`RandomInstance`](crate::RandomInstance) generation.
+///
+/// Carries the rng together with a size parameter that bounds collection lengths, and the
+/// current nesting depth. Every level of nesting halves the available size, so nested types
+/// like `Vec<Vec<String>>` stay small, and past `max_depth` collections are generated empty.
+/// It also sets how often numbers are boundary values like `0`, `MAX` or `NaN`.
+///
+/// With [`with_bytes`](Self::with_bytes), values are read from a fuzzer-provided byte slice
+/// instead, so coverage-guided fuzzers can steer generation. Implementations should draw
+/// through the context's own methods like [`gen_range`](Self::gen_range) rather than
+/// [`rng`](Self::rng), so each value comes straight from the input.
+pub struct GenContext<'a> {
+    rng: &'a mut Rng,
+    bytes: Option<&'a [u8]>,
+    size: usize,
+    depth: usize,
+    max_depth: usize,
+    edge_case_probability: f64,
+    len_override: Option<usize>,
+}
+
+impl<'a> GenContext<'a> {
+    /// Creates a context with [`DEFAULT_SIZE`] and [`DEFAULT_MAX_DEPTH`].
+    #[inline]
+    pub fn new(rng: &'a mut Rng) -> Self {
+        Self {
+            rng,
+            bytes: None,
+            size: DEFAULT_SIZE,
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+            edge_case_probability: DEFAULT_EDGE_CASE_PROBABILITY,
+            len_override: None,
+        }
+    }
+
+    /// Sets the size, the largest length a top-level collection can have.
+    #[inline]
+    #[must_use]
+    pub fn with_size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets how many collection levels can be nested before they are generated empty.
+    #[inline]
+    #[must_use]
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the probability that a number is generated as a boundary or special value.
+    ///
+    /// # Panics
+    /// If `probability` isn't in `[0, 1]`.
+    #[inline]
+    #[must_use]
+    pub fn with_edge_case_probability(mut self, probability: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "edge case probability must be in [0, 1]"
+        );
+
+        self.edge_case_probability = probability;
+        self
+    }
+
+    /// Reads values from `bytes`, as handed out by fuzzers like cargo-fuzz or AFL.
+    ///
+    /// Values are read straight from the front of the input, like the `arbitrary` crate does:
+    /// a `u8` takes one byte and a `u32` four, little-endian, and small changes to the input
+    /// make small changes to the value. Once the bytes run out they read as zeros, so every
+    /// value is the lowest it can be, collections are empty and `Option`s are `None`.
+    #[inline]
+    #[must_use]
+    pub fn with_bytes(mut self, bytes: &'a [u8]) -> Self {
+        self.bytes = Some(bytes);
+        self
+    }
+
+    /// The rng used to generate values.
+    ///
+    /// When reading from bytes, every call reseeds the rng from the next 8 bytes, or from
+    /// zero once they have run out. Prefer the context's own methods where they fit.
+    #[inline]
+    pub fn rng(&mut self) -> &mut Rng {
+        if self.bytes.is_some() {
+            let seed = u64::from_le_bytes(self.gen_bytes());
+
+            *self.rng = Rng::from_seed(seed);
+        }
+
+        self.rng
+    }
+
+    /// Generates `N` random bytes, or reads the next `N` bytes of the input.
+    #[inline]
+    pub fn gen_bytes<const N: usize>(&mut self) -> [u8; N] {
+        let mut buf = [0; N];
+
+        match &mut self.bytes {
+            Some(bytes) => take_bytes(bytes, &mut buf),
+            None => self.rng.fill_bytes(&mut buf),
+        }
+
+        buf
+    }
+
+    /// Generates a value in `range`, or reads one from the next bytes of the input, as many
+    /// as the type has up to 8.
+    ///
+    /// # Panics
+    /// If `range` is empty.
+    #[inline]
+    pub fn gen_range<T: RandomRange>(&mut self, range: impl RangeBounds<T>) -> T {
+        match &mut self.bytes {
+            Some(bytes) => {
+                let mut buf = [0; 8];
+                take_bytes(bytes, &mut buf[..size_of::<T>().min(8)]);
+
+                T::from_u64(u64::from_le_bytes(buf), range)
+            }
+            None => self.rng.gen_range(range),
+        }
+    }
+
+    /// Returns `true` with the given probability, or reads a byte of the input, where `0`
+    /// is `false` unless `probability` is `1.0`.
+    #[inline]
+    pub fn gen_bool(&mut self, probability: f64) -> bool {
+        match &mut self.bytes {
+            Some(bytes) => {
+                let mut byte = [0];
+                take_bytes(bytes, &mut byte);
+
+                (f64::from(byte[0]) + 0.5) / 256.0 > 1.0 - probability
+            }
+            None => self.rng.gen_bool(probability),
+        }
+    }
+
+    /// Whether values are read from bytes and the input has run out, so every further value
+    /// is the lowest it can be.
+    #[inline]
+    pub fn is_out_of_input(&self) -> bool {
+        self.bytes.is_some_and(<[u8]>::is_empty)
+    }
+
+    /// The size at the top level.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The current nesting depth, `0` at the top level.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// The nesting depth at which collections are generated empty.
+    #[inline]
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// The probability that a number is generated as a boundary or special value.
+    #[inline]
+    pub fn edge_case_probability(&self) -> f64 {
+        self.edge_case_probability
+    }
+
+    /// Decides whether the next number should be a boundary or special value.
+    #[inline]
+    pub fn gen_edge_case(&mut self) -> bool {
+        self.gen_bool(self.edge_case_probability)
+    }
+
+    /// Whether the depth budget is used up.
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.depth >= self.max_depth
+    }
+
+    /// The largest length a collection can have at the current depth.
+    #[inline]
+    pub fn max_len(&self) -> usize {
+        if self.is_exhausted() {
+            return 0;
+        }
+
+        self.size.checked_shr(self.depth as u32).unwrap_or(0)
+    }
+
+    /// Generates a collection length in `0..=max_len()`, or the length set by
+    /// [`override_len`](Self::override_len).
+    #[inline]
+    pub fn gen_len(&mut self) -> usize {
+        if let Some(len) = self.len_override.take() {
+            return len;
+        }
+
+        let max_len = self.max_len();
+
+        match &mut self.bytes {
+            // Only read as many bytes as `max_len` needs, none when it's zero
+            Some(bytes) => {
+                let mut buf = [0; 8];
+                let width = (usize::BITS - max_len.leading_zeros()).div_ceil(8) as usize;
+                take_bytes(bytes, &mut buf[..width]);
+
+                usize::from_u64(u64::from_le_bytes(buf), 0..=max_len)
+            }
+            None => self.rng.gen_range(0..=max_len),
+        }
+    }
+
+    /// Runs `f` with the next generated collection length picked from `len` instead of
+    /// being sized by the context, used for `#[fuzz(len = ..)]`.
+    ///
+    /// # Panics
+    /// If `len` is empty.
+    #[inline]
+    pub fn override_len<R>(
+        &mut self,
+        len: impl RangeBounds<usize>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let len = self.gen_range(len);
+
+        let previous = self.len_override.replace(len);
+        let result = f(self);
+        self.len_override = previous;
+
+        result
+    }
+
+    /// Runs `f` one nesting level deeper, used to generate the elements of a collection.
+    #[inline]
+    pub fn nested<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+
+        result
+    }
+}
+
+/// Fills `buf` from the front of `bytes`, leaving zeros once they run out.
+#[inline]
+fn take_bytes(bytes: &mut &[u8], buf: &mut [u8]) {
+    let (chunk, rest) = bytes.split_at(bytes.len().min(buf.len()));
+
+    buf[..chunk.len()].copy_from_slice(chunk);
+    *bytes = rest;
+}
+
+#[test]
+fn test_gen_context_len() {
+    use crate::RandomInstance;
+
+    let rng = &mut Rng::from_seed(7);
+    let mut ctx = GenContext::new(rng).with_size(16).with_max_depth(3);
+
+    assert_eq!(ctx.max_len(), 16);
+
+    ctx.nested(|ctx| {
+        assert_eq!(ctx.max_len(), 8);
+        assert!((0..1000).all(|_| ctx.gen_len() <= 8));
+
+        ctx.nested(|ctx| ctx.nested(|ctx| assert_eq!(ctx.max_len(), 0)));
+    });
+
+    assert_eq!(ctx.depth(), 0);
+
+    // Each nesting level halves the length, so nested collections stay bounded
+    for _ in 0..100 {
+        let nested = Vec::<Vec<String>>::random_instance(rng);
+
+        assert!(nested.len() <= DEFAULT_SIZE);
+        assert!(nested.iter().all(|v| v.len() <= DEFAULT_SIZE / 2));
+        assert!(
+            nested
+                .iter()
+                .flatten()
+                .all(|s| s.chars().count() <= DEFAULT_SIZE / 4)
+        );
+    }
+}
+
+#[test]
+fn test_gen_context_bytes() {
+    use crate::RandomInstance;
+
+    type Input = (u32, Vec<u8>, String);
+
+    let input = (0..=255).collect::<Vec<u8>>();
+
+    let a = Input::from_fuzz_bytes(&input);
+    assert_eq!(a, Input::from_fuzz_bytes(&input));
+
+    // Changing a later byte leaves the values read before it alone
+    let mut changed = input.clone();
+    changed[20] ^= 1;
+
+    let b = Input::from_fuzz_bytes(&changed);
+    assert_eq!(a.0, b.0);
+    assert_ne!(a, b);
+
+    // Values are read straight from the bytes: an edge case byte, then the value itself
+    assert_eq!(u8::from_fuzz_bytes(&[0, 42]), 42);
+    assert_eq!(u32::from_fuzz_bytes(&[0, 1, 2]), 0x0201);
+    assert_eq!(<(u8, u8)>::from_fuzz_bytes(&[0, 1, 0, 2]), (1, 2));
+
+    // Short inputs are padded with zeros
+    assert_eq!(Input::from_fuzz_bytes(&[]), Input::from_fuzz_bytes(&[0; 4]));
+}
+
+#[test]
+fn test_gen_context_bytes_exhausted() {
+    use crate::RandomInstance;
+
+    // Past the end of the input every value is the lowest it can be
+    assert_eq!(u8::from_fuzz_bytes(&[]), 0);
+    assert_eq!(f64::from_fuzz_bytes(&[]), 0.0);
+    assert_eq!(String::from_fuzz_bytes(&[]), "");
+    assert_eq!(Vec::<u8>::from_fuzz_bytes(&[]), []);
+    assert_eq!(Option::<u64>::from_fuzz_bytes(&[]), None);
+    assert_eq!(<(bool, char)>::from_fuzz_bytes(&[]), (false, ' '));
+
+    let rng = &mut Rng::from_seed(0);
+    let mut ctx = GenContext::new(rng).with_bytes(&[5]);
+
+    assert!(!ctx.is_out_of_input());
+    assert_eq!(ctx.gen_range(0..10u8), 5);
+    assert!(ctx.is_out_of_input());
+    assert!((0..64).all(|_| ctx.gen_range(3..10u8) == 3));
+}
+
+#[test]
+#[should_panic(expected = "ran out of fuzzer input")]
+fn test_gen_context_bytes_filter() {
+    use crate::RandomInstance;
+
+    fn is_adult(person: &Person) -> bool {
+        person.age >= 18
+    }
+
+    #[derive(Debug, RandomInstance)]
+    #[fuzz(filter = is_adult)]
+    struct Person {
+        age: u8,
+        name: String,
+    }
+
+    // The input passes the filter as long as it lasts, then generation gives up
+    assert!(Person::from_fuzz_bytes(&[0, 30]).age >= 18);
+    Person::from_fuzz_bytes(&[0, 5]);
+}
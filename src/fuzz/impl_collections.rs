@@ -1,116 +1,432 @@
-use crate::{Rng, fuzz::RandomInstance};
+use crate::{Alphabet, GenContext, fuzz::RandomInstance, fuzz::Shrink};
 
-const MAX_ELEMENTS: usize = 512;
+use core::cell::{Cell, RefCell};
+use core::hash::{BuildHasher, Hash};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::path::{Component, PathBuf};
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// How many draws per wanted element sets and maps make before settling for fewer elements,
+/// since the element type might not have enough distinct values (like `bool`).
+const UNIQUE_ATTEMPTS_PER_ELEMENT: usize = 4;
+
+/// Upper bound on the number of components in a generated `PathBuf`.
+const MAX_PATH_COMPONENTS: usize = 8;
 
 impl<T: RandomInstance> RandomInstance for Option<T> {
+    /// `None` once the depth budget is used up, so optional children end recursive types.
     #[inline]
-    fn random_instance(rng: &mut Rng) -> Self {
-        if rng.gen_bool(0.5) {
+    fn random_instance_with(ctx: &mut GenContext<'_>) -> Self {
+        if ctx.is_exhausted() || !ctx.gen_bool(0.5) {
             return None;
         }
 
-        Some(T::random_instance(rng))
+        Some(T::random_instance_with(ctx))
     }
 }
 
-impl<T: RandomInstance> RandomInstance for Vec<T> {
+impl<T: RandomInstance, E: RandomInstance> RandomInstance for Result<T, E> {
     #[inline]
-    fn random_instance(rng: &mut Rng) -> Self {
-        let len = rng.gen_range(0..MAX_ELEMENTS);
-        let mut vec = Vec::new();
+    fn random_instance_with(ctx: &mut GenContext<'_>) -> Self {
+        if ctx.gen_bool(0.5) {
+            Err(E::random_instance_with(ctx))
+        } else {
+            Ok(T::random_instance_with(ctx))
+        }
+    }
+}
 
-        for _ in 0..len {
-            vec.push(T::random_instance(rng));
+impl RandomInstance for () {
+    #[inline]
+    fn random_instance_with(_: &mut GenContext<'_>) -> Self {}
+}
+
+macro_rules! impl_random_instance_sequence {
+    ($($collection:ident$(: $bound:path)?),*) => {
+        $(
+            impl<T: RandomInstance $(+ $bound)?> RandomInstance for $collection<T> {
+                #[inline]
+                fn random_instance_with(ctx: &mut GenContext<'_>) -> Self {
+                    let len = ctx.gen_len();
+
+                    ctx.nested(|ctx| (0..len).map(|_| T::random_instance_with(ctx)).collect())
+                }
+            }
+        )*
+    };
+}
+
+impl_random_instance_sequence!(Vec, VecDeque, LinkedList, BinaryHeap: Ord);
+
+/// Inserts freshly generated elements until the collection reaches a generated length, or
+/// gives up after `UNIQUE_ATTEMPTS_PER_ELEMENT` draws per element.
+macro_rules! gen_unique {
+    ($ctx:expr, $collection:expr, |$inner:ident| $insert:expr) => {{
+        let len = $ctx.gen_len();
+        let mut collection = $collection;
+
+        $ctx.nested(|$inner| {
+            for _ in 0..len * UNIQUE_ATTEMPTS_PER_ELEMENT {
+                if collection.len() >= len {
+                    break;
+                }
+
+                $insert(&mut collection);
+            }
+        });
+
+        collection
+    }};
+}
+
+impl<T, S> RandomInstance for HashSet<T, S>
+where
+    T: RandomInstance + Hash + Eq,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn random_instance_with(ctx: &mut GenContext<'_>) -> Self {
+        gen_unique!(ctx, Self::default(), |ctx| |set: &mut Self| {
+            set.insert(T::random_instance_with(ctx));
+        })
+    }
+}
+
+impl<T: RandomInstance + Ord> RandomInstance for BTreeSet<T> {
+    #[inline]
+    fn random_instance_with(ctx: &mut GenContext<'_>) -> Self {
+        gen_unique!(ctx, Self::new(), |ctx| |set: &mut Self| {
+            set.insert(T::random_instance_with(ctx));
+        })
+    }
+}
+
+impl<K, V, S> RandomInstance for HashMap<K, V, S>
+where
+    K: RandomInstance + Hash + Eq,
+    V: RandomInstance,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn random_instance_with(ctx: &mut GenContext<'_>) -> Self {
+        gen_unique!(ctx, Self::default(), |ctx| |map: &mut Self| {
+            let key = K::random_instance_with(ctx);
+
+            map.entry(key)
+                .or_insert_with(|| V::random_instance_with(ctx));
+        })
+    }
+}
+
+impl<K: RandomInstance + Ord, V: RandomInstance> RandomInstance for BTreeMap<K, V> {
+    #[inline]
+    fn random_instance_with(ctx: &mut GenContext<'_>) -> Self {
+        gen_unique!(ctx, Self::new(), |ctx| |map: &mut Self| {
+            let key = K::random_instance_with(ctx);
+
+            map.entry(key)
+                .or_insert_with(|| V::random_instance_with(ctx));
+        })
+    }
+}
+
+macro_rules! impl_random_instance_tuple {
+    ($($T:ident),+) => {
+        impl<$($T: RandomInstance),+> RandomInstance for ($($T,)+) {
+            #[inline]
+            fn random_instance_with(ctx: &mut GenContext<'_>) -> Self {
+                ($($T::random_instance_with(ctx),)+)
+            }
         }
+    };
+}
+
+impl_random_instance_tuple!(T1);
+impl_random_instance_tuple!(T1, T2);
+impl_random_instance_tuple!(T1, T2, T3);
+impl_random_instance_tuple!(T1, T2, T3, T4);
+impl_random_instance_tuple!(T1, T2, T3, T4, T5);
+impl_random_instance_tuple!(T1, T2, T3, T4, T5, T6);
+impl_random_instance_tuple!(T1, T2, T3, T4, T5, T6, T7);
+impl_random_instance_tuple!(T1, T2, T3, T4, T5, T6, T7, T8);
+impl_random_instance_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_random_instance_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_random_instance_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+impl_random_instance_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
 
-        vec
+impl<T: RandomInstance + Clone, const N: usize> RandomInstance for [T; N] {
+    #[inline]
+    fn random_instance_with(ctx: &mut GenContext<'_>) -> Self {
+        let arr = [0; N];
+
+        arr.map(|_| T::random_instance_with(ctx))
     }
 }
 
-impl<T: RandomInstance> RandomInstance for std::collections::VecDeque<T> {
+macro_rules! impl_random_instance_wrapper {
+    ($($ptr:ident),*) => {
+        $(
+            impl<T: RandomInstance> RandomInstance for $ptr<T> {
+                #[inline]
+                fn random_instance_with(ctx: &mut GenContext<'_>) -> Self {
+                    $ptr::new(T::random_instance_with(ctx))
+                }
+            }
+        )*
+    };
+}
+
+impl_random_instance_wrapper!(Cell, RefCell);
+
+/// Pointers generate their contents one nesting level deeper. Every recursive type goes
+/// through one of them or a collection, so this bounds mutually recursive types too.
+macro_rules! impl_random_instance_pointer {
+    ($($ptr:ident),*) => {
+        $(
+            impl<T: RandomInstance> RandomInstance for $ptr<T> {
+                #[inline]
+                fn random_instance_with(ctx: &mut GenContext<'_>) -> Self {
+                    $ptr::new(ctx.nested(T::random_instance_with))
+                }
+            }
+        )*
+    };
+}
+
+impl_random_instance_pointer!(Box, Rc, Arc);
+
+impl RandomInstance for PathBuf {
     #[inline]
-    fn random_instance(rng: &mut Rng) -> Self {
-        let len = rng.gen_range(0..MAX_ELEMENTS);
-        let mut vec = std::collections::VecDeque::new();
+    fn random_instance_with(ctx: &mut GenContext<'_>) -> Self {
+        let len = ctx.gen_len().min(MAX_PATH_COMPONENTS);
+        let chars = Alphabet::Alphanumeric.as_str().unwrap().as_bytes();
+
+        let mut path = PathBuf::new();
+
+        if ctx.gen_bool(0.5) {
+            path.push("/");
+        }
 
         for _ in 0..len {
-            vec.push_back(T::random_instance(rng));
+            match ctx.gen_range(0..10u8) {
+                8 => path.push("."),
+                9 => path.push(".."),
+                _ => {
+                    let name_len = ctx.gen_range(1..=12u8);
+
+                    path.push(
+                        (0..name_len)
+                            .map(|_| char::from(chars[ctx.gen_range(0..chars.len())]))
+                            .collect::<String>(),
+                    );
+                }
+            }
+        }
+
+        path
+    }
+}
+
+impl<T: Shrink + Clone> Shrink for Option<T> {
+    #[inline]
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+        match self {
+            None => Box::new(core::iter::empty()),
+            Some(value) => Box::new(core::iter::once(None).chain(value.shrink().map(Some))),
+        }
+    }
+}
+
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    #[inline]
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+        let len = self.len();
+
+        if len == 0 {
+            return Box::new(core::iter::empty());
+        }
+
+        // Drop everything, then either half, then single elements
+        let smaller = core::iter::once(Vec::new())
+            .chain((len > 1).then(|| self[..len / 2].to_vec()))
+            .chain((len > 1).then(|| self[len / 2..].to_vec()))
+            .chain((0..len).filter(move |_| len > 2).map(move |i| {
+                let mut vec = self.clone();
+                vec.remove(i);
+                vec
+            }));
+
+        // Then keep the length but simplify one element at a time
+        let simpler = (0..len).flat_map(move |i| {
+            self[i].shrink().map(move |value| {
+                let mut vec = self.clone();
+                vec[i] = value;
+                vec
+            })
+        });
+
+        Box::new(smaller.chain(simpler))
+    }
+}
+
+impl<T: Shrink + Clone, E: Shrink + Clone> Shrink for Result<T, E> {
+    #[inline]
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+        match self {
+            Ok(value) => Box::new(value.shrink().map(Ok)),
+            Err(error) => Box::new(error.shrink().map(Err)),
         }
+    }
+}
 
-        vec
+impl Shrink for () {
+    #[inline]
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+        Box::new(core::iter::empty())
     }
 }
 
-// impl<T> RandomInstance for Range<T> {
-//     #[inline]
-//     fn auto_map_entities<M: EntityMapper>(&mut self, _: &mut M) {
-//         // Range<T> is considered primitive-like, so do nothing
-//     }
-// }
+/// Shrinks collections through `Vec`, since they don't support indexing.
+macro_rules! impl_shrink_via_vec {
+    ($([$($bounds:tt)*] $collection:ty => $item:ty),* $(,)?) => {
+        $(
+            impl<$($bounds)*> Shrink for $collection {
+                #[inline]
+                fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+                    let vec = self.clone().into_iter().collect::<Vec<$item>>();
 
-// impl<T: RandomInstance + Hash + Eq> RandomInstance for HashSet<T> {
-//     fn random_instance(rng: &mut Rng) -> Self {
-//         let len = rng.gen_range(0..COLLECTION_LEN);
-//         let mut set = HashSet::new();
+                    Box::new(
+                        vec.shrink()
+                            .map(|vec| vec.into_iter().collect())
+                            .collect::<Vec<_>>()
+                            .into_iter(),
+                    )
+                }
+            }
+        )*
+    };
+}
 
-//         for _ in 0..len {
-//             set.insert(T::random_instance(rng));
-//         }
+impl_shrink_via_vec!(
+    [T: Shrink + Clone] VecDeque<T> => T,
+    [T: Shrink + Clone] LinkedList<T> => T,
+    [T: Shrink + Clone + Ord] BinaryHeap<T> => T,
+    [T: Shrink + Clone + Ord] BTreeSet<T> => T,
+    [T: Shrink + Clone + Hash + Eq, S: BuildHasher + Default + Clone] HashSet<T, S> => T,
+    [K: Shrink + Clone + Ord, V: Shrink + Clone] BTreeMap<K, V> => (K, V),
+    [K: Shrink + Clone + Hash + Eq, V: Shrink + Clone, S: BuildHasher + Default + Clone]
+        HashMap<K, V, S> => (K, V),
+);
 
-//         set
-//     }
-// }
+macro_rules! impl_shrink_tuple {
+    ($($T:ident $idx:tt),+) => {
+        impl<$($T: Shrink + Clone),+> Shrink for ($($T,)+) {
+            #[inline]
+            fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+                let candidates: Box<dyn Iterator<Item = Self> + '_> = Box::new(core::iter::empty());
 
-// impl<K: RandomInstance + Hash + Eq, V: RandomInstance> RandomInstance for HashMap<K, V> {
-//     fn random_instance(rng: &mut Rng) -> Self {
-//         let len = rng.gen_range(0..COLLECTION_LEN);
-//         let mut map = HashMap::new();
+                // Simplify one element at a time, keeping the others
+                $(
+                    let candidates = Box::new(candidates.chain(self.$idx.shrink().map(move |value| {
+                        let mut tuple = self.clone();
+                        tuple.$idx = value;
+                        tuple
+                    })));
+                )+
 
-//         for _ in 0..len {
-//             map.insert(K::random_instance(rng), V::random_instance(rng));
-//         }
+                candidates
+            }
+        }
+    };
+}
 
-//         map
-//     }
-// }
+impl_shrink_tuple!(T1 0);
+impl_shrink_tuple!(T1 0, T2 1);
+impl_shrink_tuple!(T1 0, T2 1, T3 2);
+impl_shrink_tuple!(T1 0, T2 1, T3 2, T4 3);
+impl_shrink_tuple!(T1 0, T2 1, T3 2, T4 3, T5 4);
+impl_shrink_tuple!(T1 0, T2 1, T3 2, T4 3, T5 4, T6 5);
+impl_shrink_tuple!(T1 0, T2 1, T3 2, T4 3, T5 4, T6 5, T7 6);
+impl_shrink_tuple!(T1 0, T2 1, T3 2, T4 3, T5 4, T6 5, T7 6, T8 7);
+impl_shrink_tuple!(T1 0, T2 1, T3 2, T4 3, T5 4, T6 5, T7 6, T8 7, T9 8);
+impl_shrink_tuple!(T1 0, T2 1, T3 2, T4 3, T5 4, T6 5, T7 6, T8 7, T9 8, T10 9);
+impl_shrink_tuple!(T1 0, T2 1, T3 2, T4 3, T5 4, T6 5, T7 6, T8 7, T9 8, T10 9, T11 10);
+impl_shrink_tuple!(T1 0, T2 1, T3 2, T4 3, T5 4, T6 5, T7 6, T8 7, T9 8, T10 9, T11 10, T12 11);
 
-impl<T1: RandomInstance, T2: RandomInstance> RandomInstance for (T1, T2) {
+impl<T: Shrink + Clone, const N: usize> Shrink for [T; N] {
     #[inline]
-    fn random_instance(rng: &mut Rng) -> Self {
-        (T1::random_instance(rng), T2::random_instance(rng))
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+        Box::new((0..N).flat_map(move |i| {
+            self[i].shrink().map(move |value| {
+                let mut arr = self.clone();
+                arr[i] = value;
+                arr
+            })
+        }))
     }
 }
 
-impl<T1: RandomInstance, T2: RandomInstance, T3: RandomInstance> RandomInstance for (T1, T2, T3) {
+macro_rules! impl_shrink_pointer {
+    ($($ptr:ident),*) => {
+        $(
+            impl<T: Shrink> Shrink for $ptr<T> {
+                #[inline]
+                fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+                    Box::new((**self).shrink().map($ptr::new))
+                }
+            }
+        )*
+    };
+}
+
+impl_shrink_pointer!(Box, Rc, Arc);
+
+impl<T: Shrink + Copy> Shrink for Cell<T> {
     #[inline]
-    fn random_instance(rng: &mut Rng) -> Self {
-        (
-            T1::random_instance(rng),
-            T2::random_instance(rng),
-            T3::random_instance(rng),
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+        let value = self.get();
+
+        Box::new(
+            value
+                .shrink()
+                .map(Cell::new)
+                .collect::<Vec<_>>()
+                .into_iter(),
         )
     }
 }
 
-impl<T1: RandomInstance, T2: RandomInstance, T3: RandomInstance, T4: RandomInstance> RandomInstance
-    for (T1, T2, T3, T4)
-{
+impl<T: Shrink> Shrink for RefCell<T> {
     #[inline]
-    fn random_instance(rng: &mut Rng) -> Self {
-        (
-            T1::random_instance(rng),
-            T2::random_instance(rng),
-            T3::random_instance(rng),
-            T4::random_instance(rng),
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+        let value = self.borrow();
+
+        Box::new(
+            value
+                .shrink()
+                .map(RefCell::new)
+                .collect::<Vec<_>>()
+                .into_iter(),
         )
     }
 }
 
-impl<T: RandomInstance + Clone, const N: usize> RandomInstance for [T; N] {
+impl Shrink for PathBuf {
     #[inline]
-    fn random_instance(rng: &mut Rng) -> Self {
-        let arr = [0; N];
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+        let components = self.components().collect::<Vec<Component<'_>>>();
+        let len = components.len();
+
+        // Drop everything, then single components
+        let candidates = core::iter::once(PathBuf::new())
+            .filter(move |_| len > 0)
+            .chain((0..len).filter(move |_| len > 1).map(move |i| {
+                let mut components = components.clone();
+                components.remove(i);
+                components.into_iter().collect()
+            }));
 
-        arr.map(|_| T::random_instance(rng))
+        Box::new(candidates)
     }
 }
@@ -1,56 +1,230 @@
-use crate::{Rng, fuzz::RandomInstance};
+use crate::{GenContext, fuzz::RandomInstance, fuzz::Shrink};
 
-macro_rules! impl_random_instance {
+/// Printable ASCII, the same characters `RandomValue` generates.
+impl RandomInstance for char {
+    #[inline]
+    fn random_instance_with(ctx: &mut GenContext<'_>) -> Self {
+        char::from(0x20 + ctx.gen_range(0..96u8))
+    }
+}
+
+/// Integers are uniform, except that with the context's edge case probability they are a
+/// boundary value (`0`, `1`, `MIN`, `MAX`, powers of two) possibly moved by one or negated.
+macro_rules! impl_random_instance_int {
     ($($t:ty),*) => {
         $(
             impl RandomInstance for $t {
                 #[inline]
-                fn random_instance(rng: &mut Rng) -> Self {
-                    rng.gen_value()
+                fn random_instance_with(ctx: &mut GenContext<'_>) -> Self {
+                    if !ctx.gen_edge_case() {
+                        return <$t>::from_le_bytes(ctx.gen_bytes());
+                    }
+
+                    const SPECIAL: [$t; 5] = [0, 1, 2, <$t>::MIN, <$t>::MAX];
+
+                    let base = if ctx.gen_bool(0.5) {
+                        SPECIAL[ctx.gen_range(0..SPECIAL.len())]
+                    } else {
+                        (1 as $t) << ctx.gen_range(0..<$t>::BITS)
+                    };
+
+                    match ctx.gen_range(0..4u8) {
+                        0 => base.wrapping_sub(1),
+                        1 => base.wrapping_add(1),
+                        2 => base.wrapping_neg(),
+                        _ => base,
+                    }
                 }
             }
         )*
     };
 }
 
-// Implement for all primitives
-impl_random_instance!(
-    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, char, String
+impl_random_instance_int!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
 );
 
-impl RandomInstance for f32 {
-    #[inline]
-    fn random_instance(rng: &mut Rng) -> Self {
-        if rng.gen_bool(0.08) {
-            return 0.0;
-        }
+/// Floats are in `[0, 1)` or have uniformly random finite bits, with a random sign, except
+/// that with the context's edge case probability they are a special value: zeros, infinities,
+/// NaN, the extremes, epsilon or a subnormal.
+macro_rules! impl_random_instance_float {
+    ($($t:ident: $bits:ty),*) => {
+        $(
+            impl RandomInstance for $t {
+                #[inline]
+                fn random_instance_with(ctx: &mut GenContext<'_>) -> Self {
+                    const SPECIAL: [$t; 12] = [
+                        0.0,
+                        -0.0,
+                        1.0,
+                        -1.0,
+                        $t::NAN,
+                        $t::INFINITY,
+                        $t::NEG_INFINITY,
+                        $t::MIN_POSITIVE,
+                        $t::MAX,
+                        $t::MIN,
+                        $t::EPSILON,
+                        $t::from_bits(1),
+                    ];
+
+                    let value = if ctx.gen_edge_case() {
+                        if ctx.gen_bool(0.8) {
+                            return SPECIAL[ctx.gen_range(0..SPECIAL.len())];
+                        }
 
-        if rng.gen_bool(0.3) {
-            return rng.gen_value::<f32>();
-        }
+                        // A random subnormal, all exponent bits clear
+                        $t::from_bits(ctx.gen_range(1..(1 << ($t::MANTISSA_DIGITS - 1))))
+                    } else if ctx.gen_bool(0.3) {
+                        ctx.gen_range(0.0..1.0)
+                    } else {
+                        // Ends once the input runs out, since zero bits are finite
+                        loop {
+                            let value = $t::from_bits(<$bits>::from_le_bytes(ctx.gen_bytes()));
 
-        rng.gen_value::<f32>() * f32::MAX
+                            if value.is_finite() {
+                                break value.abs();
+                            }
+                        }
+                    };
+
+                    if ctx.gen_bool(0.5) { -value } else { value }
+                }
+            }
+        )*
+    };
+}
+
+impl_random_instance_float!(f32: u32, f64: u64);
+
+impl RandomInstance for String {
+    #[inline]
+    fn random_instance_with(ctx: &mut GenContext<'_>) -> Self {
+        let len = ctx.gen_len();
+
+        (0..len).map(|_| char::random_instance_with(ctx)).collect()
     }
 }
 
-impl RandomInstance for f64 {
+impl RandomInstance for bool {
     #[inline]
-    fn random_instance(rng: &mut Rng) -> Self {
-        if rng.gen_bool(0.08) {
-            return 0.0;
-        }
+    fn random_instance_with(ctx: &mut GenContext<'_>) -> Self {
+        ctx.gen_bool(0.5)
+    }
+}
+
+/// Candidates moving `x` towards zero: `0`, then `x - x/2`, `x - x/4`, ..., `x - 1`.
+macro_rules! shrink_towards_zero {
+    ($x:expr) => {{
+        let x = $x;
+
+        core::iter::once(0)
+            .chain(
+                core::iter::successors(Some(x / 2), |&d| (d / 2 != 0).then_some(d / 2))
+                    .take_while(|&d| d != 0)
+                    .map(move |d| x - d),
+            )
+            .filter(move |&c| c != x)
+    }};
+}
+
+macro_rules! impl_shrink_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Shrink for $t {
+                #[inline]
+                fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+                    Box::new(shrink_towards_zero!(*self))
+                }
+            }
+        )*
+    };
+}
+
+impl_shrink_unsigned!(u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_shrink_signed {
+    ($($t:ty),*) => {
+        $(
+            impl Shrink for $t {
+                #[inline]
+                fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+                    let x = *self;
+
+                    // Try the positive value first, then move towards zero
+                    let positive = (x < 0).then(|| x.checked_neg()).flatten();
+
+                    Box::new(
+                        shrink_towards_zero!(x)
+                            .take(1)
+                            .chain(positive)
+                            .chain(shrink_towards_zero!(x).skip(1)),
+                    )
+                }
+            }
+        )*
+    };
+}
 
-        if rng.gen_bool(0.3) {
-            return rng.gen_value::<f64>();
-        }
+impl_shrink_signed!(i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_shrink_float {
+    ($($t:ty),*) => {
+        $(
+            impl Shrink for $t {
+                #[inline]
+                fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+                    let x = *self;
 
-        rng.gen_value::<f64>() * f64::MAX
+                    if x == 0.0 {
+                        return Box::new(core::iter::empty());
+                    }
+
+                    if !x.is_finite() {
+                        return Box::new(core::iter::once(0.0));
+                    }
+
+                    let candidates = [
+                        Some(0.0),
+                        (x < 0.0).then_some(-x),
+                        (x.trunc() != x).then_some(x.trunc()),
+                        Some(x / 2.0),
+                    ];
+
+                    Box::new(candidates.into_iter().flatten())
+                }
+            }
+        )*
+    };
+}
+
+impl_shrink_float!(f32, f64);
+
+impl Shrink for bool {
+    #[inline]
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+        Box::new(self.then_some(false).into_iter())
     }
 }
 
-impl RandomInstance for bool {
+impl Shrink for char {
+    #[inline]
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+        Box::new((*self != 'a').then_some('a').into_iter())
+    }
+}
+
+impl Shrink for String {
     #[inline]
-    fn random_instance(rng: &mut Rng) -> Self {
-        rng.gen_bool(0.5)
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+        let chars = self.chars().collect::<Vec<_>>();
+
+        Box::new(
+            chars
+                .shrink()
+                .map(|chars| chars.into_iter().collect::<String>())
+                .collect::<Vec<_>>()
+                .into_iter(),
+        )
     }
 }
@@ -1,13 +1,42 @@
 use crate::Rng;
 
+mod check;
+mod context;
 mod impl_collections;
 mod impl_primitives;
+mod shrink;
 
+pub use check::*;
+pub use context::*;
 #[allow(unused_imports)]
 pub use impl_collections::*;
 #[allow(unused_imports)]
 pub use impl_primitives::*;
+pub use shrink::*;
 
-pub trait RandomInstance {
-    fn random_instance(rng: &mut Rng) -> Self;
+pub use fuzz_derive::{RandomInstance, Shrink};
+
+/// Generates arbitrary instances of a type for fuzzing and randomized testing.
+///
+/// Derive it with `#[derive(RandomInstance)]`, available with the `fuzz` feature.
+///
+/// Implementors generate from a [`GenContext`], which bounds how large collections get.
+pub trait RandomInstance: Sized {
+    /// Generates an instance, sizing collections from `ctx`.
+    fn random_instance_with(ctx: &mut GenContext<'_>) -> Self;
+
+    /// Generates an instance with the default size.
+    #[inline]
+    fn random_instance(rng: &mut Rng) -> Self {
+        Self::random_instance_with(&mut GenContext::new(rng))
+    }
+
+    /// Generates an instance from a fuzzer-provided input, like the `data` of a cargo-fuzz
+    /// `fuzz_target!`. See [`GenContext::with_bytes`].
+    #[inline]
+    fn from_fuzz_bytes(bytes: &[u8]) -> Self {
+        let rng = &mut Rng::from_seed(0);
+
+        Self::random_instance_with(&mut GenContext::new(rng).with_bytes(bytes))
+    }
 }
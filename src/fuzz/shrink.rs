@@ -0,0 +1,56 @@
+/// Upper bound on the number of candidates `minimize` tries, so values that shrink very
+/// slowly (like floats halving towards zero) still finish quickly.
+const MAX_SHRINK_ATTEMPTS: usize = 10_000;
+
+/// Produces progressively simpler versions of a value, so that failing fuzz inputs can be
+/// minimized into readable counterexamples.
+///
+/// Derive it with `#[derive(Shrink)]`, available with the `fuzz` feature.
+pub trait Shrink: Sized {
+    /// Returns candidates that are simpler than `self`, roughly simplest first.
+    ///
+    /// A value that can't be simplified any further returns an empty iterator.
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_>;
+}
+
+/// Shrinks a failing `input` for as long as `fails` keeps returning `true`, and returns the
+/// smallest failing input that was found.
+///
+/// Each step greedily takes the first candidate from [`Shrink::shrink`] that still fails.
+#[inline]
+pub fn minimize<T: Shrink>(mut input: T, mut fails: impl FnMut(&T) -> bool) -> T {
+    let mut attempts = 0;
+
+    loop {
+        let next = input.shrink().find(|candidate| {
+            attempts += 1;
+
+            attempts <= MAX_SHRINK_ATTEMPTS && fails(candidate)
+        });
+
+        match next {
+            Some(simpler) => input = simpler,
+            None => return input,
+        }
+    }
+}
+
+#[test]
+fn test_minimize() {
+    assert_eq!(minimize(987_654u32, |&x| x >= 1234), 1234);
+    assert_eq!(minimize(-987_654i64, |&x| x <= -50), -50);
+    assert_eq!(minimize(123.456f64, |&x| x > 1.0), 1.5);
+    assert!(!minimize(true, |_| true));
+
+    let input = (0..200).collect::<Vec<u32>>();
+    assert_eq!(minimize(input, |v| v.iter().any(|&x| x >= 100)), vec![100]);
+
+    let input = "hello world".to_string();
+    assert_eq!(minimize(input, |s| s.contains('w')), "w");
+
+    let input = (Some(vec![5u8, 200, 7]), 99i32);
+    assert_eq!(
+        minimize(input, |(v, _)| v.as_ref().is_some_and(|v| v.len() >= 2)),
+        (Some(vec![0, 0]), 0)
+    );
+}
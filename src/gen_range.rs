@@ -1,10 +1,36 @@
-use crate::Rng;
+use crate::{Rng, gen_value::FILL_LANES, rng::u64_to_f64};
 
 use core::ops::Bound::{Excluded, Included, Unbounded};
 use core::ops::RangeBounds;
 
 pub trait RandomRange {
     fn gen_range(rng: &mut Rng, range: impl RangeBounds<Self>) -> Self;
+
+    /// Maps `x` into `range`, with `0` giving the lowest value.
+    ///
+    /// Used to read values straight from fuzzer input. The default seeds an rng from `x`,
+    /// so implementations should override it with a direct mapping.
+    #[inline]
+    fn from_u64(x: u64, range: impl RangeBounds<Self>) -> Self
+    where
+        Self: Sized,
+    {
+        Self::gen_range(&mut Rng::from_seed(x), range)
+    }
+
+    /// Fills `dest` with random values in `range`.
+    ///
+    /// Produces the same values as calling `gen_range` for each element in order;
+    /// implementations may override it with a batched path.
+    #[inline]
+    fn fill_range(rng: &mut Rng, dest: &mut [Self], range: impl RangeBounds<Self>)
+    where
+        Self: Sized,
+    {
+        for value in dest {
+            *value = Self::gen_range(rng, (range.start_bound(), range.end_bound()));
+        }
+    }
 }
 
 impl Rng {
@@ -14,30 +40,60 @@ impl Rng {
     }
 }
 
+/// Resolves an integer range to its start and the number of values in it.
+macro_rules! int_range_span {
+    ($t:ty, $range:expr) => {{
+        let start = match $range.start_bound() {
+            Included(&n) => n,
+            Excluded(&n) => n + 1,
+            Unbounded => <$t>::MIN,
+        };
+        let end = match $range.end_bound() {
+            Included(&n) => n + 1,
+            Excluded(&n) => n,
+            Unbounded => <$t>::MAX,
+        };
+
+        if start >= end {
+            panic!("Empty or invalid range: [{start}, {end})");
+        }
+
+        (start, (end - start) as u64)
+    }};
+}
+
 macro_rules! impl_random_range_int {
     ($($t:ty),*) => {
         $(
             impl RandomRange for $t {
                 #[inline]
                 fn gen_range(rng: &mut Rng, range: impl RangeBounds<Self>) -> Self {
-                    let start = match range.start_bound() {
-                        Included(&n) => n,
-                        Excluded(&n) => n + 1,
-                        Unbounded => <$t>::MIN,
-                    };
-                    let end = match range.end_bound() {
-                        Included(&n) => n + 1,
-                        Excluded(&n) => n,
-                        Unbounded => <$t>::MAX,
-                    };
-
-                    if start >= end {
-                        panic!("Empty or invalid range: [{start}, {end})");
-                    }
+                    Self::from_u64(rng.next_u64(), range)
+                }
 
-                    let span = (end - start) as u64;
+                #[inline]
+                fn from_u64(x: u64, range: impl RangeBounds<Self>) -> Self {
+                    let (start, span) = int_range_span!($t, range);
 
-                    (rng.next_u64() % span) as $t + start
+                    (x % span) as $t + start
+                }
+
+                #[inline]
+                fn fill_range(rng: &mut Rng, dest: &mut [Self], range: impl RangeBounds<Self>) {
+                    let (start, span) = int_range_span!($t, range);
+                    let mut chunks = dest.chunks_exact_mut(FILL_LANES);
+
+                    for chunk in &mut chunks {
+                        let lanes = rng.next_u64_lanes::<FILL_LANES>();
+
+                        for (value, lane) in chunk.iter_mut().zip(lanes) {
+                            *value = (lane % span) as $t + start;
+                        }
+                    }
+
+                    for value in chunks.into_remainder() {
+                        *value = (rng.next_u64() % span) as $t + start;
+                    }
                 }
             }
         )*
@@ -49,6 +105,11 @@ impl_random_range_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
 impl RandomRange for f32 {
     #[inline]
     fn gen_range(rng: &mut Rng, range: impl RangeBounds<Self>) -> Self {
+        Self::from_u64(rng.next_u64(), range)
+    }
+
+    #[inline]
+    fn from_u64(x: u64, range: impl RangeBounds<Self>) -> Self {
         let start = match range.start_bound() {
             Included(&n) => n,
             Excluded(&n) => next_after_f32(n, f32::INFINITY),
@@ -62,13 +123,18 @@ impl RandomRange for f32 {
 
         assert!((start < end), "Invalid float range: ({start}, {end})");
 
-        (rng.next_f64() as f32).mul_add(end - start, start)
+        (u64_to_f64(x) as f32).mul_add(end - start, start)
     }
 }
 
 impl RandomRange for f64 {
     #[inline]
     fn gen_range(rng: &mut Rng, range: impl RangeBounds<Self>) -> Self {
+        Self::from_u64(rng.next_u64(), range)
+    }
+
+    #[inline]
+    fn from_u64(x: u64, range: impl RangeBounds<Self>) -> Self {
         let start = match range.start_bound() {
             Included(&n) => n,
             Excluded(&n) => next_after_f64(n, f64::INFINITY),
@@ -82,7 +148,7 @@ impl RandomRange for f64 {
 
         assert!(start < end, "Invalid float range: [{start}, {end})");
 
-        rng.next_f64().mul_add(end - start, start)
+        u64_to_f64(x).mul_add(end - start, start)
     }
 }
 
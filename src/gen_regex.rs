@@ -0,0 +1,594 @@
+use crate::{Distribution, Rng};
+
+use core::fmt;
+
+/// Default number of extra repetitions allowed by unbounded quantifiers like `*`, `+`
+/// and `{n,}`.
+pub const DEFAULT_MAX_REPEAT: u32 = 8;
+
+/// First and last character of printable ASCII, the universe for `.` and negated classes.
+const PRINTABLE: (char, char) = (' ', '~');
+
+/// A regular expression that generates matching strings.
+///
+/// Supports literals, `.`, character classes like `[a-z_]` and `[^0-9]`, the escapes `\d`,
+/// `\w`, `\s` and their negations, groups `(..)` and `(?:..)`, alternation `|`, and the
+/// quantifiers `*`, `+`, `?`, `{n}`, `{n,}` and `{n,m}`. Anchors `^` and `$` are accepted
+/// and ignored, since every generated string matches the whole pattern.
+///
+/// `.` and negated classes only produce printable ASCII, and unbounded quantifiers repeat
+/// at most [`DEFAULT_MAX_REPEAT`] times more than their minimum.
+#[derive(Clone, Debug)]
+pub struct Regex {
+    root: Node,
+    max_repeat: u32,
+}
+
+/// Error returned by [`Regex::new`] for patterns that can't be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegexError {
+    position: usize,
+    message: String,
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Literal(char),
+    Class(Class),
+    Concat(Vec<Node>),
+    Alternation(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: u32,
+        max: Option<u32>,
+    },
+}
+
+/// A set of characters, stored as sorted, non-overlapping inclusive ranges.
+#[derive(Clone, Debug, Default)]
+struct Class {
+    ranges: Vec<(char, char)>,
+    len: u32,
+}
+
+impl Regex {
+    /// Parses `pattern`.
+    #[inline]
+    pub fn new(pattern: &str) -> Result<Self, RegexError> {
+        let mut parser = Parser {
+            chars: pattern.chars().collect(),
+            pos: 0,
+        };
+
+        let root = parser.parse_alternation()?;
+
+        if parser.pos < parser.chars.len() {
+            return Err(parser.error("unmatched `)`"));
+        }
+
+        Ok(Self {
+            root,
+            max_repeat: DEFAULT_MAX_REPEAT,
+        })
+    }
+
+    /// Sets how many repetitions unbounded quantifiers allow past their minimum.
+    ///
+    /// Defaults to [`DEFAULT_MAX_REPEAT`].
+    #[inline]
+    #[must_use]
+    pub fn with_max_repeat(mut self, max_repeat: u32) -> Self {
+        self.max_repeat = max_repeat;
+        self
+    }
+
+    /// Generates a random string matching the pattern.
+    #[inline]
+    pub fn sample(&self, rng: &mut Rng) -> String {
+        let mut s = String::new();
+
+        self.sample_node(&self.root, rng, &mut s);
+
+        s
+    }
+
+    fn sample_node(&self, node: &Node, rng: &mut Rng, s: &mut String) {
+        match node {
+            Node::Literal(c) => s.push(*c),
+            Node::Class(class) => s.push(class.sample(rng)),
+            Node::Concat(nodes) => {
+                for node in nodes {
+                    self.sample_node(node, rng, s);
+                }
+            }
+            Node::Alternation(branches) => {
+                let branch = &branches[rng.gen_range(0..branches.len())];
+
+                self.sample_node(branch, rng, s);
+            }
+            Node::Repeat { node, min, max } => {
+                let max = max.unwrap_or(min.saturating_add(self.max_repeat));
+
+                for _ in 0..rng.gen_range(*min..=max) {
+                    self.sample_node(node, rng, s);
+                }
+            }
+        }
+    }
+}
+
+impl Distribution<String> for &Regex {
+    #[inline]
+    fn sample(&self, rng: &mut Rng) -> String {
+        Regex::sample(self, rng)
+    }
+}
+
+impl Rng {
+    /// Returns a random string matching the regular expression `pattern`.
+    ///
+    /// Parses the pattern on every call; build a [`Regex`] once to generate many strings.
+    ///
+    /// # Panics
+    /// If `pattern` isn't a valid [`Regex`].
+    #[inline]
+    pub fn gen_from_regex(&mut self, pattern: &str) -> String {
+        match Regex::new(pattern) {
+            Ok(regex) => regex.sample(self),
+            Err(e) => panic!("{e}"),
+        }
+    }
+}
+
+impl RegexError {
+    /// The character index in the pattern where parsing failed.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// What went wrong.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for RegexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid regex at {}: {}", self.position, self.message)
+    }
+}
+
+impl std::error::Error for RegexError {}
+
+impl Class {
+    fn from_ranges(ranges: &[(char, char)]) -> Self {
+        let mut class = Self::default();
+
+        for &(lo, hi) in ranges {
+            class.add(lo, hi);
+        }
+
+        class
+    }
+
+    fn add(&mut self, lo: char, hi: char) {
+        // Keep the surrogate gap out of the ranges, so every index maps to a `char`
+        if lo <= '\u{D7FF}' && hi >= '\u{E000}' {
+            self.ranges.push((lo, '\u{D7FF}'));
+            self.ranges.push(('\u{E000}', hi));
+        } else {
+            self.ranges.push((lo, hi));
+        }
+
+        self.ranges.sort_unstable();
+
+        let mut merged: Vec<(char, char)> = Vec::with_capacity(self.ranges.len());
+
+        for &(lo, hi) in &self.ranges {
+            match merged.last_mut() {
+                Some(last) if lo as u32 <= last.1 as u32 + 1 => last.1 = last.1.max(hi),
+                _ => merged.push((lo, hi)),
+            }
+        }
+
+        self.len = merged
+            .iter()
+            .map(|&(lo, hi)| hi as u32 - lo as u32 + 1)
+            .sum();
+        self.ranges = merged;
+    }
+
+    fn extend(&mut self, other: &Class) {
+        for &(lo, hi) in &other.ranges {
+            self.add(lo, hi);
+        }
+    }
+
+    /// The printable ASCII characters not in the class.
+    fn negate(&self) -> Self {
+        let mut negated = Self::default();
+        let mut next = PRINTABLE.0 as u32;
+
+        for &(lo, hi) in &self.ranges {
+            if lo as u32 > next && next <= PRINTABLE.1 as u32 {
+                let end = (lo as u32 - 1).min(PRINTABLE.1 as u32);
+
+                negated.add(char::from_u32(next).unwrap(), char::from_u32(end).unwrap());
+            }
+
+            next = next.max(hi as u32 + 1);
+        }
+
+        if next <= PRINTABLE.1 as u32 {
+            negated.add(char::from_u32(next).unwrap(), PRINTABLE.1);
+        }
+
+        negated
+    }
+
+    fn sample(&self, rng: &mut Rng) -> char {
+        let mut index = rng.gen_range(0..self.len);
+
+        for &(lo, hi) in &self.ranges {
+            let len = hi as u32 - lo as u32 + 1;
+
+            if index < len {
+                return char::from_u32(lo as u32 + index).unwrap();
+            }
+
+            index -= len;
+        }
+
+        unreachable!()
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn error(&self, message: impl Into<String>) -> RegexError {
+        RegexError {
+            position: self.pos,
+            message: message.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let matches = self.peek() == Some(c);
+
+        if matches {
+            self.pos += 1;
+        }
+
+        matches
+    }
+
+    fn next(&mut self) -> Result<char, RegexError> {
+        let c = self
+            .peek()
+            .ok_or_else(|| self.error("unexpected end of pattern"))?;
+        self.pos += 1;
+
+        Ok(c)
+    }
+
+    fn parse_alternation(&mut self) -> Result<Node, RegexError> {
+        let mut branches = vec![self.parse_concat()?];
+
+        while self.eat('|') {
+            branches.push(self.parse_concat()?);
+        }
+
+        Ok(if branches.len() == 1 {
+            branches.pop().unwrap()
+        } else {
+            Node::Alternation(branches)
+        })
+    }
+
+    fn parse_concat(&mut self) -> Result<Node, RegexError> {
+        let mut nodes = Vec::new();
+
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+
+            let atom = self.parse_atom()?;
+            nodes.push(self.parse_quantifier(atom)?);
+        }
+
+        Ok(Node::Concat(nodes))
+    }
+
+    fn parse_atom(&mut self) -> Result<Node, RegexError> {
+        Ok(match self.next()? {
+            '(' => {
+                // Non-capturing and named groups generate like plain ones
+                if self.eat('?') {
+                    if self.eat('P') || self.eat('<') {
+                        if matches!(self.peek(), Some('=' | '!')) {
+                            return Err(self.error("lookbehind is not supported"));
+                        }
+
+                        while self.next()? != '>' {}
+                    } else if !self.eat(':') {
+                        return Err(self.error("unsupported group flag"));
+                    }
+                }
+
+                let node = self.parse_alternation()?;
+
+                if !self.eat(')') {
+                    return Err(self.error("unclosed group"));
+                }
+
+                node
+            }
+            '[' => Node::Class(self.parse_class()?),
+            '.' => Node::Class(Class::from_ranges(&[PRINTABLE])),
+            '^' | '$' => Node::Concat(Vec::new()),
+            '\\' => match self.parse_escape()? {
+                Escape::Char(c) => Node::Literal(c),
+                Escape::Class(class) => Node::Class(class),
+            },
+            '*' | '+' | '?' | '{' => {
+                self.pos -= 1;
+
+                return Err(self.error("quantifier without anything to repeat"));
+            }
+            c => Node::Literal(c),
+        })
+    }
+
+    fn parse_quantifier(&mut self, node: Node) -> Result<Node, RegexError> {
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => {
+                self.pos += 1;
+
+                let min = self.parse_number()?;
+
+                let max = if self.eat(',') {
+                    if self.peek() == Some('}') {
+                        None
+                    } else {
+                        Some(self.parse_number()?)
+                    }
+                } else {
+                    Some(min)
+                };
+
+                if self.peek() != Some('}') {
+                    return Err(self.error("expected `}`"));
+                }
+
+                if max.is_some_and(|max| max < min) {
+                    return Err(self.error("repetition maximum is less than the minimum"));
+                }
+
+                (min, max)
+            }
+            _ => return Ok(node),
+        };
+
+        self.pos += 1;
+
+        // Lazy quantifiers match the same strings
+        self.eat('?');
+
+        if matches!(self.peek(), Some('*' | '+' | '?' | '{')) {
+            return Err(self.error("quantifier without anything to repeat"));
+        }
+
+        Ok(Node::Repeat {
+            node: Box::new(node),
+            min,
+            max,
+        })
+    }
+
+    fn parse_number(&mut self) -> Result<u32, RegexError> {
+        let start = self.pos;
+
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+
+        if start == self.pos {
+            return Err(self.error("expected a number"));
+        }
+
+        self.chars[start..self.pos]
+            .iter()
+            .collect::<String>()
+            .parse()
+            .map_err(|_| self.error("repetition count is too large"))
+    }
+
+    /// Parses a class after its opening `[`.
+    fn parse_class(&mut self) -> Result<Class, RegexError> {
+        let negated = self.eat('^');
+        let mut class = Class::default();
+        let mut first = true;
+
+        loop {
+            let c = self
+                .next()
+                .map_err(|_| self.error("unclosed character class"))?;
+
+            // A `]` right after the opening bracket is a literal
+            if c == ']' && !first {
+                break;
+            }
+
+            first = false;
+
+            let lo = match c {
+                '\\' => match self.parse_escape()? {
+                    Escape::Char(c) => c,
+                    Escape::Class(other) => {
+                        class.extend(&other);
+                        continue;
+                    }
+                },
+                c => c,
+            };
+
+            // `-` is a literal at the end of the class
+            if self.peek() == Some('-') && self.chars.get(self.pos + 1) != Some(&']') {
+                self.pos += 1;
+
+                let hi = match self.next()? {
+                    '\\' => match self.parse_escape()? {
+                        Escape::Char(c) => c,
+                        Escape::Class(_) => return Err(self.error("invalid class range")),
+                    },
+                    c => c,
+                };
+
+                if hi < lo {
+                    return Err(self.error("class range is out of order"));
+                }
+
+                class.add(lo, hi);
+            } else {
+                class.add(lo, lo);
+            }
+        }
+
+        let class = if negated { class.negate() } else { class };
+
+        if class.len == 0 {
+            return Err(self.error("character class matches nothing"));
+        }
+
+        Ok(class)
+    }
+
+    /// Parses an escape sequence after its `\`.
+    fn parse_escape(&mut self) -> Result<Escape, RegexError> {
+        const DIGIT: &[(char, char)] = &[('0', '9')];
+        const WORD: &[(char, char)] = &[('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')];
+        const SPACE: &[(char, char)] = &[('\t', '\r'), (' ', ' ')];
+
+        Ok(match self.next()? {
+            'd' => Escape::Class(Class::from_ranges(DIGIT)),
+            'w' => Escape::Class(Class::from_ranges(WORD)),
+            's' => Escape::Class(Class::from_ranges(SPACE)),
+            'D' => Escape::Class(Class::from_ranges(DIGIT).negate()),
+            'W' => Escape::Class(Class::from_ranges(WORD).negate()),
+            'S' => Escape::Class(Class::from_ranges(SPACE).negate()),
+            'n' => Escape::Char('\n'),
+            'r' => Escape::Char('\r'),
+            't' => Escape::Char('\t'),
+            '0' => Escape::Char('\0'),
+            'x' => {
+                let hex = self
+                    .chars
+                    .get(self.pos..self.pos + 2)
+                    .filter(|hex| hex.iter().all(char::is_ascii_hexdigit));
+                let code = hex
+                    .and_then(|hex| u32::from_str_radix(&hex.iter().collect::<String>(), 16).ok());
+
+                let Some(code) = code else {
+                    return Err(self.error("expected two hex digits after `\\x`"));
+                };
+
+                self.pos += 2;
+
+                Escape::Char(char::from_u32(code).unwrap())
+            }
+            c if c.is_ascii_alphanumeric() => {
+                self.pos -= 1;
+
+                return Err(self.error(format!("unsupported escape `\\{c}`")));
+            }
+            c => Escape::Char(c),
+        })
+    }
+}
+
+enum Escape {
+    Char(char),
+    Class(Class),
+}
+
+#[test]
+fn test_gen_from_regex() {
+    let rng = &mut Rng::from_seed(0);
+
+    for _ in 0..1000 {
+        let email = rng.gen_from_regex("[a-z]{3,8}@example\\.com");
+        let (user, domain) = email.split_once('@').unwrap();
+
+        assert!((3..=8).contains(&user.len()));
+        assert!(user.chars().all(|c| c.is_ascii_lowercase()));
+        assert_eq!(domain, "example.com");
+
+        let id = rng.gen_from_regex(r"^(?:ID|id)-\d{4}[^\d\s]?$");
+        assert!(id.starts_with("ID-") || id.starts_with("id-"));
+        assert!(id[3..7].chars().all(|c| c.is_ascii_digit()));
+        assert!(
+            id[7..]
+                .chars()
+                .all(|c| c.is_ascii_graphic() && !c.is_ascii_digit())
+        );
+
+        let word = rng.gen_from_regex(r"\w+[-\]x]*");
+        assert!(!word.is_empty());
+        assert!(
+            word.chars()
+                .all(|c| c.is_ascii_alphanumeric() || "_-]x".contains(c))
+        );
+
+        assert!(rng.gen_from_regex("(ab)*").len() <= 2 * DEFAULT_MAX_REPEAT as usize);
+        assert!("αβγ".contains(&rng.gen_from_regex("[α-γ]")));
+    }
+
+    // Every branch of an alternation is reachable
+    let regex = Regex::new("cat|dog|bird").unwrap();
+    let animals = regex.sample_iter(rng).take(100).collect::<Vec<_>>();
+
+    for animal in ["cat", "dog", "bird"] {
+        assert!(animals.iter().any(|a| a == animal));
+    }
+
+    assert_eq!(
+        rng.gen_from_regex(r"(?<year>\x32\x30)(?P<rest>\x3F)"),
+        "20?"
+    );
+
+    let regex = Regex::new("a*").unwrap().with_max_repeat(0);
+    assert_eq!(regex.sample(rng), "");
+
+    for (pattern, position) in [
+        ("a{3,1}", 5),
+        ("(ab", 3),
+        ("ab)", 2),
+        ("*a", 0),
+        ("[]", 2),
+        ("\\q", 1),
+        ("(?<=a)b", 3),
+        ("(?<!a)b", 3),
+        ("\\x+1", 2),
+        ("\\x4", 2),
+    ] {
+        assert_eq!(
+            Regex::new(pattern).unwrap_err().position(),
+            position,
+            "{pattern}"
+        );
+    }
+}
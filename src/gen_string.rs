@@ -0,0 +1,172 @@
+use crate::Rng;
+
+use core::ops::RangeBounds;
+
+/// A set of characters to generate random `char`s and `String`s from.
+///
+/// Custom alphabets can be given as a `&str`, which converts into [`Alphabet::Custom`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Alphabet<'a> {
+    /// `a-z`
+    Lowercase,
+    /// `A-Z`
+    Uppercase,
+    /// `0-9`
+    Digits,
+    /// `A-Z`, `a-z` and `0-9`
+    Alphanumeric,
+    /// `0-9` and `a-f`
+    Hex,
+    /// `A-Z`, `a-z`, `0-9`, `+` and `/`
+    Base64,
+    /// `A-Z`, `a-z`, `0-9`, `-` and `_`, safe for URLs and file names
+    Base64Url,
+    /// Printable ASCII, from space (`0x20`) up to and including `~` (`0x7E`)
+    AsciiPrintable,
+    /// Any valid Unicode scalar value, i.e. any code point except the surrogates
+    Unicode,
+    /// Every character of the given string, each equally likely
+    Custom(&'a str),
+}
+
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+const ALPHANUMERIC: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+const HEX: &str = "0123456789abcdef";
+const BASE64: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_URL: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const ASCII_PRINTABLE: &str = " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~";
+
+/// Number of Unicode scalar values: all code points minus the 2048 surrogates.
+const UNICODE_SCALAR_COUNT: u32 = 0x11_0000 - 0x800;
+
+impl<'a> From<&'a str> for Alphabet<'a> {
+    #[inline]
+    fn from(chars: &'a str) -> Self {
+        Alphabet::Custom(chars)
+    }
+}
+
+impl<'a> Alphabet<'a> {
+    /// Returns the alphabet's characters, or `None` for [`Alphabet::Unicode`].
+    #[inline]
+    pub(crate) fn as_str(&self) -> Option<&'a str> {
+        Some(match *self {
+            Alphabet::Lowercase => LOWERCASE,
+            Alphabet::Uppercase => UPPERCASE,
+            Alphabet::Digits => DIGITS,
+            Alphabet::Alphanumeric => ALPHANUMERIC,
+            Alphabet::Hex => HEX,
+            Alphabet::Base64 => BASE64,
+            Alphabet::Base64Url => BASE64_URL,
+            Alphabet::AsciiPrintable => ASCII_PRINTABLE,
+            Alphabet::Unicode => return None,
+            Alphabet::Custom(chars) => chars,
+        })
+    }
+}
+
+impl Rng {
+    /// Returns a random character from `alphabet`.
+    ///
+    /// # Panics
+    /// If a custom alphabet is empty.
+    #[inline]
+    pub fn gen_char_from<'a>(&mut self, alphabet: impl Into<Alphabet<'a>>) -> char {
+        match alphabet.into().as_str() {
+            None => self.gen_unicode_char(),
+            Some(chars) if chars.is_ascii() => {
+                assert!(!chars.is_empty(), "cannot sample from an empty alphabet");
+
+                chars.as_bytes()[self.gen_range(0..chars.len())] as char
+            }
+            Some(chars) => {
+                let count = chars.chars().count();
+
+                chars.chars().nth(self.gen_range(0..count)).unwrap()
+            }
+        }
+    }
+
+    /// Returns a uniformly random valid Unicode scalar value.
+    ///
+    /// Unlike `gen_value::<char>()`, which only yields printable ASCII, any `char`
+    /// can be returned.
+    #[inline]
+    pub fn gen_unicode_char(&mut self) -> char {
+        let mut code = self.gen_range(0..UNICODE_SCALAR_COUNT);
+
+        // Skip over the surrogate range `0xD800..=0xDFFF`
+        if code >= 0xD800 {
+            code += 0x800;
+        }
+
+        char::from_u32(code).unwrap()
+    }
+
+    /// Returns a string with a random length in `len`, made of random characters from
+    /// `alphabet`.
+    ///
+    /// # Panics
+    /// - If `len` is empty
+    /// - If a custom alphabet is empty
+    #[inline]
+    pub fn gen_string<'a>(
+        &mut self,
+        alphabet: impl Into<Alphabet<'a>>,
+        len: impl RangeBounds<usize>,
+    ) -> String {
+        let len = self.gen_range(len);
+
+        match alphabet.into().as_str() {
+            None => (0..len).map(|_| self.gen_unicode_char()).collect(),
+            Some(chars) if chars.is_ascii() => {
+                assert!(!chars.is_empty(), "cannot sample from an empty alphabet");
+
+                let bytes = chars.as_bytes();
+
+                (0..len)
+                    .map(|_| bytes[self.gen_range(0..bytes.len())] as char)
+                    .collect()
+            }
+            Some(chars) => {
+                // Index the characters once instead of walking the string for every one
+                let chars = chars.chars().collect::<Vec<_>>();
+
+                (0..len)
+                    .map(|_| chars[self.gen_range(0..chars.len())])
+                    .collect()
+            }
+        }
+    }
+}
+
+#[test]
+fn test_gen_string() {
+    let rng = &mut Rng::from_seed(0);
+
+    for _ in 0..1000 {
+        assert!(rng.gen_char_from(Alphabet::Hex).is_ascii_hexdigit());
+        assert!(
+            rng.gen_char_from(Alphabet::Alphanumeric)
+                .is_ascii_alphanumeric()
+        );
+        assert!("äöü".contains(rng.gen_char_from("äöü")));
+
+        let id = rng.gen_string(Alphabet::Base64Url, 8..=16);
+        assert!((8..=16).contains(&id.len()));
+        assert!(id.chars().all(|c| BASE64_URL.contains(c)));
+
+        let s = rng.gen_string("αβγ", 3..4);
+        assert_eq!(s.chars().count(), 3);
+        assert!(s.chars().all(|c| "αβγ".contains(c)));
+    }
+
+    // Every letter of a small alphabet shows up
+    let s = rng.gen_string(Alphabet::Lowercase, 1000..=1000);
+    assert!(LOWERCASE.chars().all(|c| s.contains(c)));
+
+    // Unicode reaches outside the Basic Multilingual Plane
+    assert!((0..1000).any(|_| rng.gen_unicode_char() as u32 > 0xFFFF));
+}
@@ -1,7 +1,29 @@
-use crate::Rng;
+use crate::{Rng, rng::u64_to_f64};
+
+use core::cmp::Ordering;
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use core::num::{NonZero, Wrapping};
+use core::time::Duration;
+
+/// Number of values generated per batch by the bulk `fill` implementations.
+pub(crate) const FILL_LANES: usize = 8;
 
 pub trait RandomValue {
     fn gen_value(rng: &mut Rng) -> Self;
+
+    /// Fills `dest` with random values.
+    ///
+    /// Produces the same values as calling `gen_value` for each element in order;
+    /// implementations may override it with a batched path.
+    #[inline]
+    fn fill(rng: &mut Rng, dest: &mut [Self])
+    where
+        Self: Sized,
+    {
+        for value in dest {
+            *value = Self::gen_value(rng);
+        }
+    }
 }
 
 impl Rng {
@@ -19,14 +41,48 @@ macro_rules! impl_random_value_int {
                 fn gen_value(rng: &mut Rng) -> Self {
                     rng.next_u64() as $t
                 }
+
+                #[inline]
+                fn fill(rng: &mut Rng, dest: &mut [Self]) {
+                    let mut chunks = dest.chunks_exact_mut(FILL_LANES);
+
+                    for chunk in &mut chunks {
+                        let lanes = rng.next_u64_lanes::<FILL_LANES>();
+
+                        for (value, lane) in chunk.iter_mut().zip(lanes) {
+                            *value = lane as $t;
+                        }
+                    }
+
+                    for value in chunks.into_remainder() {
+                        *value = rng.next_u64() as $t;
+                    }
+                }
             }
         )*
     };
 }
 
-impl_random_value_int!(
-    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
-);
+impl_random_value_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+macro_rules! impl_random_value_int_128 {
+    ($($t:ty),*) => {
+        $(
+            impl RandomValue for $t {
+                /// Built from two `u64`s, high half first, to cover the full range
+                #[inline]
+                fn gen_value(rng: &mut Rng) -> Self {
+                    let high = rng.next_u64() as u128;
+                    let low = rng.next_u64() as u128;
+
+                    (high << 64 | low) as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_random_value_int_128!(u128, i128);
 
 impl RandomValue for f32 {
     /// In the range: (0..1.0)
@@ -34,6 +90,23 @@ impl RandomValue for f32 {
     fn gen_value(rng: &mut Rng) -> Self {
         rng.next_f64() as f32
     }
+
+    #[inline]
+    fn fill(rng: &mut Rng, dest: &mut [Self]) {
+        let mut chunks = dest.chunks_exact_mut(FILL_LANES);
+
+        for chunk in &mut chunks {
+            let lanes = rng.next_u64_lanes::<FILL_LANES>();
+
+            for (value, lane) in chunk.iter_mut().zip(lanes) {
+                *value = u64_to_f64(lane) as f32;
+            }
+        }
+
+        for value in chunks.into_remainder() {
+            *value = rng.next_f64() as f32;
+        }
+    }
 }
 
 impl RandomValue for f64 {
@@ -42,6 +115,23 @@ impl RandomValue for f64 {
     fn gen_value(rng: &mut Rng) -> Self {
         rng.next_f64()
     }
+
+    #[inline]
+    fn fill(rng: &mut Rng, dest: &mut [Self]) {
+        let mut chunks = dest.chunks_exact_mut(FILL_LANES);
+
+        for chunk in &mut chunks {
+            let lanes = rng.next_u64_lanes::<FILL_LANES>();
+
+            for (value, lane) in chunk.iter_mut().zip(lanes) {
+                *value = u64_to_f64(lane);
+            }
+        }
+
+        for value in chunks.into_remainder() {
+            *value = rng.next_f64();
+        }
+    }
 }
 
 impl RandomValue for char {
@@ -64,3 +154,246 @@ impl RandomValue for String {
         s
     }
 }
+
+impl RandomValue for bool {
+    /// `true` or `false` with equal probability
+    #[inline]
+    fn gen_value(rng: &mut Rng) -> Self {
+        rng.gen_bool(0.5)
+    }
+}
+
+impl RandomValue for () {
+    #[inline]
+    fn gen_value(_: &mut Rng) -> Self {}
+}
+
+macro_rules! impl_random_value_tuple {
+    ($($name:ident),*) => {
+        impl<$($name: RandomValue),*> RandomValue for ($($name,)*) {
+            /// Each element generated in order
+            #[inline]
+            fn gen_value(rng: &mut Rng) -> Self {
+                ($($name::gen_value(rng),)*)
+            }
+        }
+    };
+}
+
+impl_random_value_tuple!(A);
+impl_random_value_tuple!(A, B);
+impl_random_value_tuple!(A, B, C);
+impl_random_value_tuple!(A, B, C, D);
+impl_random_value_tuple!(A, B, C, D, E);
+impl_random_value_tuple!(A, B, C, D, E, F);
+impl_random_value_tuple!(A, B, C, D, E, F, G);
+impl_random_value_tuple!(A, B, C, D, E, F, G, H);
+impl_random_value_tuple!(A, B, C, D, E, F, G, H, I);
+impl_random_value_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_random_value_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_random_value_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+impl<T: RandomValue, const N: usize> RandomValue for [T; N] {
+    /// Each element generated in order
+    #[inline]
+    fn gen_value(rng: &mut Rng) -> Self {
+        core::array::from_fn(|_| T::gen_value(rng))
+    }
+}
+
+impl<T: RandomValue> RandomValue for Option<T> {
+    /// `None` or `Some` with equal probability
+    #[inline]
+    fn gen_value(rng: &mut Rng) -> Self {
+        if rng.gen_bool(0.5) {
+            return None;
+        }
+
+        Some(T::gen_value(rng))
+    }
+}
+
+impl<T: RandomValue> RandomValue for Wrapping<T> {
+    /// Same domain as `T`
+    #[inline]
+    fn gen_value(rng: &mut Rng) -> Self {
+        Wrapping(T::gen_value(rng))
+    }
+}
+
+macro_rules! impl_random_value_non_zero {
+    ($($t:ty),*) => {
+        $(
+            impl RandomValue for NonZero<$t> {
+                /// Any value of the integer type except zero
+                #[inline]
+                fn gen_value(rng: &mut Rng) -> Self {
+                    loop {
+                        if let Some(n) = NonZero::new(rng.gen_value::<$t>()) {
+                            return n;
+                        }
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_random_value_non_zero!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+impl RandomValue for Duration {
+    /// Any number of whole seconds (the full `u64` range) plus `0..1_000_000_000` nanoseconds
+    #[inline]
+    fn gen_value(rng: &mut Rng) -> Self {
+        Duration::new(rng.gen_value(), rng.gen_range(0..1_000_000_000))
+    }
+}
+
+impl RandomValue for Ordering {
+    /// `Less`, `Equal` or `Greater` with equal probability
+    #[inline]
+    fn gen_value(rng: &mut Rng) -> Self {
+        match rng.gen_range(0..3) {
+            0 => Ordering::Less,
+            1 => Ordering::Equal,
+            _ => Ordering::Greater,
+        }
+    }
+}
+
+impl RandomValue for Ipv4Addr {
+    /// Any address, including reserved ranges
+    #[inline]
+    fn gen_value(rng: &mut Rng) -> Self {
+        Ipv4Addr::from_bits(rng.gen_value())
+    }
+}
+
+impl RandomValue for Ipv6Addr {
+    /// Any address, including reserved ranges
+    #[inline]
+    fn gen_value(rng: &mut Rng) -> Self {
+        Ipv6Addr::from_bits(rng.gen_value())
+    }
+}
+
+impl RandomValue for IpAddr {
+    /// An IPv4 or IPv6 address with equal probability
+    #[inline]
+    fn gen_value(rng: &mut Rng) -> Self {
+        if rng.gen_bool(0.5) {
+            IpAddr::V4(rng.gen_value())
+        } else {
+            IpAddr::V6(rng.gen_value())
+        }
+    }
+}
+
+impl RandomValue for SocketAddrV4 {
+    /// Any address and any port, including `0`
+    #[inline]
+    fn gen_value(rng: &mut Rng) -> Self {
+        SocketAddrV4::new(rng.gen_value(), rng.gen_value())
+    }
+}
+
+impl RandomValue for SocketAddrV6 {
+    /// Any address and any port, including `0`, with a flow info and scope id of `0`
+    #[inline]
+    fn gen_value(rng: &mut Rng) -> Self {
+        SocketAddrV6::new(rng.gen_value(), rng.gen_value(), 0, 0)
+    }
+}
+
+impl RandomValue for SocketAddr {
+    /// An IPv4 or IPv6 socket address with equal probability
+    #[inline]
+    fn gen_value(rng: &mut Rng) -> Self {
+        if rng.gen_bool(0.5) {
+            SocketAddr::V4(rng.gen_value())
+        } else {
+            SocketAddr::V6(rng.gen_value())
+        }
+    }
+}
+
+#[test]
+fn test_gen_value_compound() {
+    let rng = &mut Rng::from_seed(0);
+
+    let (bytes, _flag) = rng.gen_value::<([u8; 16], bool)>();
+    assert!(bytes.iter().any(|&b| b != 0));
+
+    let mut seen = [false; 3];
+
+    for _ in 0..100 {
+        assert_ne!(rng.gen_value::<NonZero<u8>>().get(), 0);
+        assert_ne!(rng.gen_value::<NonZero<i128>>().get(), 0);
+        assert!(rng.gen_value::<Duration>().subsec_nanos() < 1_000_000_000);
+
+        seen[(rng.gen_value::<Ordering>() as i8 + 1) as usize] = true;
+    }
+
+    assert_eq!(seen, [true; 3]);
+    assert!((0..100).any(|_| rng.gen_value::<Option<u8>>().is_none()));
+    assert!((0..100).any(|_| rng.gen_value::<IpAddr>().is_ipv6()));
+
+    // 128-bit integers cover their whole range, not just the low 64 bits
+    assert!((0..100).any(|_| rng.gen_value::<NonZero<i128>>().get() < 0));
+    assert!((0..100).any(|_| rng.gen_value::<NonZero<u128>>().get() > u64::MAX as u128));
+    assert!((0..100).any(|_| rng.gen_value::<Wrapping<u128>>().0 > u64::MAX as u128));
+}
+
+#[test]
+fn test_derive_random_value() {
+    use rng_derive::RandomValue;
+
+    fn always_seven(_: &mut Rng) -> u8 {
+        7
+    }
+
+    #[derive(Debug, RandomValue)]
+    struct Monster {
+        #[rng(range = 1..=100)]
+        health: u32,
+        #[rng(value = 5)]
+        level: u8,
+        #[rng(with = always_seven)]
+        luck: u8,
+        #[rng(skip)]
+        name: String,
+        alive: bool,
+    }
+
+    #[derive(Debug, RandomValue)]
+    struct Pair<T>(T, #[rng(range = 0.0..1.0)] f32);
+
+    #[derive(Debug, RandomValue)]
+    enum Shape {
+        Point,
+        Circle(#[rng(range = 1.0..10.0)] f64),
+        Rect { w: u8, h: u8 },
+    }
+
+    let rng = &mut Rng::from_seed(0);
+
+    for _ in 0..100 {
+        let m = rng.gen_value::<Monster>();
+
+        assert!((1..=100).contains(&m.health));
+        assert_eq!((m.level, m.luck), (5, 7));
+        assert!(m.name.is_empty());
+
+        let Pair(_, f) = rng.gen_value::<Pair<(u8, bool)>>();
+        assert!((0.0..1.0).contains(&f));
+
+        if let Shape::Circle(r) = rng.gen_value::<Shape>() {
+            assert!((1.0..10.0).contains(&r));
+        }
+    }
+
+    assert!((0..100).any(|_| rng.gen_value::<Monster>().alive));
+    assert!((0..100).any(|_| matches!(rng.gen_value::<Shape>(), Shape::Rect { .. })));
+}
@@ -0,0 +1,289 @@
+use crate::Rng;
+
+use core::f64::consts::TAU;
+
+/// Floating-point types that geometric samples can be returned as.
+///
+/// Sampling is done in `f64` internally and converted on return.
+/// Implemented for `f32` and `f64`.
+pub trait GeomFloat: Copy {
+    /// Converts a sample computed in `f64`, rounding to the nearest value for `f32`.
+    fn from_f64(value: f64) -> Self;
+
+    /// Widens the value to `f64` so it can be used in the internal computations.
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_geom_float {
+    ($($t:ty),*) => {
+        $(
+            impl GeomFloat for $t {
+                #[inline]
+                fn from_f64(value: f64) -> Self {
+                    value as $t
+                }
+
+                #[inline]
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_geom_float!(f32, f64);
+
+impl Rng {
+    /// Returns a random point on the unit circle (the perimeter, radius `1.0`).
+    #[inline]
+    pub fn gen_on_circle<T: GeomFloat>(&mut self) -> [T; 2] {
+        let (sin, cos) = (self.next_f64() * TAU).sin_cos();
+
+        [T::from_f64(cos), T::from_f64(sin)]
+    }
+
+    /// Returns a uniformly distributed random point inside the unit disc.
+    #[inline]
+    pub fn gen_in_disc<T: GeomFloat>(&mut self) -> [T; 2] {
+        let r = self.next_f64().sqrt();
+        let (sin, cos) = (self.next_f64() * TAU).sin_cos();
+
+        [T::from_f64(r * cos), T::from_f64(r * sin)]
+    }
+
+    /// Returns a uniformly distributed random point inside the annulus centered on the origin
+    /// with the given inner and outer radii.
+    ///
+    /// # Panics
+    /// If `inner` is negative or greater than `outer`.
+    #[inline]
+    pub fn gen_in_annulus<T: GeomFloat>(&mut self, inner: T, outer: T) -> [T; 2] {
+        let (inner, outer) = (inner.to_f64(), outer.to_f64());
+
+        assert!(
+            0.0 <= inner && inner <= outer,
+            "Invalid annulus radii: [{inner}, {outer}]"
+        );
+
+        // Sample the radius by area so points don't cluster on the inner edge
+        let r = self
+            .next_f64()
+            .mul_add(outer * outer - inner * inner, inner * inner)
+            .sqrt();
+        let (sin, cos) = (self.next_f64() * TAU).sin_cos();
+
+        [T::from_f64(r * cos), T::from_f64(r * sin)]
+    }
+
+    /// Returns a random point on the surface of the unit sphere.
+    #[inline]
+    pub fn gen_on_sphere<T: GeomFloat>(&mut self) -> [T; 3] {
+        let z = self.next_f64().mul_add(2.0, -1.0);
+        let r = (1.0 - z * z).sqrt();
+        let (sin, cos) = (self.next_f64() * TAU).sin_cos();
+
+        [T::from_f64(r * cos), T::from_f64(r * sin), T::from_f64(z)]
+    }
+
+    /// Returns a uniformly distributed random point inside the unit ball.
+    #[inline]
+    pub fn gen_in_ball<T: GeomFloat>(&mut self) -> [T; 3] {
+        let [x, y, z] = self.gen_on_sphere::<f64>();
+        let r = self.next_f64().cbrt();
+
+        [T::from_f64(x * r), T::from_f64(y * r), T::from_f64(z * r)]
+    }
+
+    /// Returns a random unit-length vector in `N` dimensions, uniformly distributed over
+    /// the surface of the unit hypersphere.
+    ///
+    /// # Panics
+    /// If `N` is zero.
+    #[inline]
+    pub fn gen_unit_vector<T: GeomFloat, const N: usize>(&mut self) -> [T; N] {
+        assert!(N > 0, "Cannot generate a zero-dimensional unit vector");
+
+        // Normalizing a vector of independent gaussians gives a uniform direction
+        loop {
+            let v: [f64; N] = core::array::from_fn(|_| self.gen_gaussian());
+            let len = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+
+            if len > f64::EPSILON {
+                return v.map(|x| T::from_f64(x / len));
+            }
+        }
+    }
+
+    /// Returns a uniformly distributed random point inside the triangle `a`, `b`, `c`.
+    ///
+    /// Works for triangles embedded in any number of dimensions.
+    #[inline]
+    pub fn gen_in_triangle<T: GeomFloat, const N: usize>(
+        &mut self,
+        a: [T; N],
+        b: [T; N],
+        c: [T; N],
+    ) -> [T; N] {
+        let mut u = self.next_f64();
+        let mut v = self.next_f64();
+
+        // Fold points from the far half of the parallelogram back into the triangle
+        if u + v > 1.0 {
+            u = 1.0 - u;
+            v = 1.0 - v;
+        }
+
+        core::array::from_fn(|i| {
+            let a = a[i].to_f64();
+            let ab = b[i].to_f64() - a;
+            let ac = c[i].to_f64() - a;
+
+            T::from_f64(u.mul_add(ab, v.mul_add(ac, a)))
+        })
+    }
+
+    /// Returns a standard normally distributed `f64` (mean `0.0`, standard deviation `1.0`)
+    /// using the Box-Muller transform.
+    #[inline]
+    pub(crate) fn gen_gaussian(&mut self) -> f64 {
+        // `1.0 - x` keeps the logarithm away from zero
+        let r = (-2.0 * (1.0 - self.next_f64()).ln()).sqrt();
+
+        r * (self.next_f64() * TAU).cos()
+    }
+}
+
+/// Samples uniformly distributed points inside a triangulated polygon.
+///
+/// Triangles are picked proportionally to their area, so every part of the polygon
+/// is equally likely.
+#[derive(Clone, Debug)]
+pub struct PolygonSampler<T> {
+    triangles: Vec<[[T; 2]; 3]>,
+    cdf: Vec<f64>,
+}
+
+impl<T: GeomFloat> PolygonSampler<T> {
+    /// Creates a new PolygonSampler from the polygon's vertices and a triangulation of it,
+    /// given as triples of indices into `vertices`.
+    ///
+    /// # Panics
+    /// - If a triangle index is out of bounds
+    /// - If the polygon has zero area
+    #[inline]
+    pub fn new(vertices: &[[T; 2]], triangles: &[[usize; 3]]) -> Self {
+        let mut cdf = Vec::with_capacity(triangles.len());
+        let mut tris = Vec::with_capacity(triangles.len());
+
+        let mut total = 0.0;
+
+        for &[a, b, c] in triangles {
+            let tri = [vertices[a], vertices[b], vertices[c]];
+            let area = triangle_area(tri);
+
+            if area > 0.0 {
+                total += area;
+                tris.push(tri);
+                cdf.push(total);
+            }
+        }
+
+        assert!(total > 0.0, "polygon must have a positive area");
+
+        // Normalize the CDF to end at 1.0
+        for val in &mut cdf {
+            *val /= total;
+        }
+
+        Self {
+            triangles: tris,
+            cdf,
+        }
+    }
+
+    /// Creates a new PolygonSampler from the vertices of a convex polygon, in order.
+    ///
+    /// # Panics
+    /// If the polygon has zero area.
+    #[inline]
+    pub fn from_convex(vertices: &[[T; 2]]) -> Self {
+        let triangles = (1..vertices.len().saturating_sub(1))
+            .map(|i| [0, i, i + 1])
+            .collect::<Vec<_>>();
+
+        Self::new(vertices, &triangles)
+    }
+
+    #[inline]
+    pub fn sample(&self, rng: &mut Rng) -> [T; 2] {
+        let r = rng.next_f64();
+
+        // Binary search to find the first cdf value >= r
+        let i = match self.cdf.binary_search_by(|v| v.partial_cmp(&r).unwrap()) {
+            Ok(i) | Err(i) => i.min(self.cdf.len() - 1),
+        };
+
+        let [a, b, c] = self.triangles[i];
+
+        rng.gen_in_triangle(a, b, c)
+    }
+}
+
+fn triangle_area<T: GeomFloat>([a, b, c]: [[T; 2]; 3]) -> f64 {
+    let [ax, ay] = a.map(T::to_f64);
+    let [bx, by] = b.map(T::to_f64);
+    let [cx, cy] = c.map(T::to_f64);
+
+    ((bx - ax) * (cy - ay) - (cx - ax) * (by - ay)).abs() * 0.5
+}
+
+#[test]
+fn test_geometry() {
+    let rng = &mut Rng::from_seed(0);
+
+    for _ in 0..1000 {
+        let [x, y] = rng.gen_on_circle::<f64>();
+        assert!((x.hypot(y) - 1.0).abs() < 1e-9);
+
+        let [x, y] = rng.gen_in_disc::<f32>();
+        assert!(x.hypot(y) <= 1.0);
+
+        let [x, y] = rng.gen_in_annulus(2.0, 3.0);
+        assert!((2.0..=3.0 + 1e-9).contains(&f64::hypot(x, y)));
+
+        let [x, y, z] = rng.gen_on_sphere::<f64>();
+        assert!(((x * x + y * y + z * z).sqrt() - 1.0).abs() < 1e-9);
+
+        let [x, y, z] = rng.gen_in_ball::<f64>();
+        assert!((x * x + y * y + z * z).sqrt() <= 1.0 + 1e-9);
+
+        let v = rng.gen_unit_vector::<f64, 5>();
+        assert!((v.iter().map(|x| x * x).sum::<f64>().sqrt() - 1.0).abs() < 1e-9);
+
+        let [x, y] = rng.gen_in_triangle([0.0, 0.0], [1.0, 0.0], [0.0, 1.0]);
+        assert!(x >= 0.0 && y >= 0.0 && x + y <= 1.0 + 1e-9);
+    }
+}
+
+#[test]
+fn test_polygon_sampler() {
+    let rng = &mut Rng::from_seed(0);
+
+    // Unit square
+    let square = PolygonSampler::from_convex(&[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]);
+
+    let mut lower = 0;
+
+    for _ in 0..10_000 {
+        let [x, y] = square.sample(rng);
+        assert!((0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y));
+
+        if y < 0.5 {
+            lower += 1;
+        }
+    }
+
+    // Both halves are equally likely
+    assert!((4500..5500).contains(&lower));
+}
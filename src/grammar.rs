@@ -0,0 +1,679 @@
+use crate::{Distribution, Regex, Rng};
+
+use core::fmt;
+
+/// Default number of rule expansions that can be nested before a [`Grammar`] only picks
+/// the alternatives that finish soonest.
+pub const DEFAULT_GRAMMAR_DEPTH: u32 = 16;
+
+/// Default number of repetitions allowed by `*`, `+` and `{ .. }` in a [`Grammar`].
+pub const DEFAULT_GRAMMAR_REPEAT: u32 = 4;
+
+/// A context-free grammar in BNF/EBNF notation that generates random sentences.
+///
+/// Each rule is written `name ::= expression`, optionally ending with `;`. Names are
+/// identifiers or `<bracketed words>`, and the first rule is where generation starts.
+/// Expressions combine:
+/// - terminals in single or double quotes, like `"+"` or `'\n'`
+/// - terminals matching a [`Regex`] between slashes, like `/[0-9]+/`
+/// - other rules by name
+/// - alternatives `a | b`, grouping `( .. )`, optional parts `[ .. ]` or `a?`, and
+///   repetition `{ .. }`, `a*` or `a+`
+///
+/// Text after a `#` is a comment.
+///
+/// ```
+/// # use rng::{Grammar, Rng};
+/// let grammar = Grammar::new(r#"
+///     expr   ::= term (("+" | "-") term)*
+///     term   ::= number | "(" expr ")"
+///     number ::= /[1-9][0-9]{0,2}/
+/// "#).unwrap();
+///
+/// let sentence = grammar.sample(&mut Rng::new());
+/// ```
+///
+/// Past the maximum depth, only the alternatives that finish in the fewest expansions are
+/// picked and repetitions stop, so generation always terminates.
+#[derive(Clone, Debug)]
+pub struct Grammar {
+    rules: Vec<Rule>,
+    start: usize,
+    max_depth: u32,
+    max_repeat: u32,
+}
+
+/// Error returned by [`Grammar::new`] for grammars that can't be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GrammarError {
+    line: usize,
+    message: String,
+}
+
+#[derive(Clone, Debug)]
+struct Rule {
+    name: String,
+    expr: Expr,
+    /// The fewest nested expansions this rule needs to produce a sentence.
+    depth: u32,
+}
+
+#[derive(Clone, Debug)]
+enum Expr {
+    Terminal(String),
+    Regex(Regex),
+    Rule(usize),
+    Sequence(Vec<Expr>),
+    Choice(Vec<Expr>),
+    Repeat { expr: Box<Expr>, min: u32, max: u32 },
+}
+
+impl Grammar {
+    /// Parses the rules in `source`, starting generation at the first one.
+    pub fn new(source: &str) -> Result<Self, GrammarError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            pos: 0,
+            names: Vec::new(),
+            references: Vec::new(),
+        };
+
+        // Collect the rule names first, so rules can refer to ones defined later
+        let mut definitions = Vec::new();
+
+        for i in 0..tokens.len() {
+            if let (Token::Name(name), Some(Token::Define)) =
+                (&tokens[i].0, tokens.get(i + 1).map(|t| &t.0))
+            {
+                if parser.names.contains(name) {
+                    return Err(GrammarError::new(
+                        tokens[i].1,
+                        format!("rule `{name}` is defined twice"),
+                    ));
+                }
+
+                parser.names.push(name.clone());
+                definitions.push(i);
+            }
+        }
+
+        if definitions.first() != Some(&0) {
+            let line = tokens.first().map_or(1, |t| t.1);
+
+            return Err(GrammarError::new(
+                line,
+                "expected a rule definition `name ::= ..`",
+            ));
+        }
+
+        let mut rules = Vec::with_capacity(definitions.len());
+
+        for (name, &start) in parser.names.clone().into_iter().zip(&definitions) {
+            parser.pos = start + 2;
+
+            let expr = parser.parse_choice()?;
+
+            parser.eat(&Token::Semicolon);
+
+            if parser.pos < tokens.len() && !parser.at_definition() {
+                return Err(parser.error("unexpected token"));
+            }
+
+            rules.push(Rule {
+                name,
+                expr,
+                depth: u32::MAX,
+            });
+        }
+
+        if let Some((name, line)) = parser
+            .references
+            .iter()
+            .find(|(name, _)| !parser.names.contains(name))
+        {
+            return Err(GrammarError::new(
+                *line,
+                format!("rule `{name}` is not defined"),
+            ));
+        }
+
+        let mut grammar = Self {
+            rules,
+            start: 0,
+            max_depth: DEFAULT_GRAMMAR_DEPTH,
+            max_repeat: DEFAULT_GRAMMAR_REPEAT,
+        };
+
+        grammar.compute_depths();
+
+        if let Some(i) = grammar.rules.iter().position(|rule| rule.depth == u32::MAX) {
+            return Err(GrammarError::new(
+                tokens[definitions[i]].1,
+                format!(
+                    "rule `{}` can never finish expanding",
+                    grammar.rules[i].name
+                ),
+            ));
+        }
+
+        Ok(grammar)
+    }
+
+    /// Sets the rule generation starts at.
+    ///
+    /// # Panics
+    /// If there's no rule called `rule`.
+    #[inline]
+    #[must_use]
+    pub fn with_start(mut self, rule: &str) -> Self {
+        self.start = self.expect_rule(rule);
+        self
+    }
+
+    /// Sets how many rule expansions can be nested before only the alternatives that finish
+    /// soonest are picked. Defaults to [`DEFAULT_GRAMMAR_DEPTH`].
+    #[inline]
+    #[must_use]
+    pub fn with_max_depth(mut self, max_depth: u32) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets how many times `*`, `+` and `{ .. }` repeat at most. Defaults to
+    /// [`DEFAULT_GRAMMAR_REPEAT`].
+    #[inline]
+    #[must_use]
+    pub fn with_max_repeat(mut self, max_repeat: u32) -> Self {
+        self.max_repeat = max_repeat;
+        self
+    }
+
+    /// Generates a random sentence from the start rule.
+    #[inline]
+    pub fn sample(&self, rng: &mut Rng) -> String {
+        let mut s = String::new();
+
+        self.expand(&self.rules[self.start].expr, self.max_depth, rng, &mut s);
+
+        s
+    }
+
+    /// Generates a random sentence from the rule called `rule`.
+    ///
+    /// # Panics
+    /// If there's no rule called `rule`.
+    #[inline]
+    pub fn sample_rule(&self, rng: &mut Rng, rule: &str) -> String {
+        let mut s = String::new();
+
+        self.expand(
+            &self.rules[self.expect_rule(rule)].expr,
+            self.max_depth,
+            rng,
+            &mut s,
+        );
+
+        s
+    }
+
+    fn rule_index(&self, name: &str) -> Option<usize> {
+        self.rules.iter().position(|rule| rule.name == name)
+    }
+
+    fn expect_rule(&self, name: &str) -> usize {
+        self.rule_index(name)
+            .unwrap_or_else(|| panic!("grammar has no rule called `{name}`"))
+    }
+
+    /// Finds each rule's fewest nested expansions by iterating until nothing changes.
+    fn compute_depths(&mut self) {
+        loop {
+            let mut changed = false;
+
+            for i in 0..self.rules.len() {
+                let depth = self.depth(&self.rules[i].expr);
+
+                if depth < self.rules[i].depth {
+                    self.rules[i].depth = depth;
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    /// The fewest nested rule expansions `expr` needs, `u32::MAX` if it can't finish.
+    fn depth(&self, expr: &Expr) -> u32 {
+        match expr {
+            Expr::Terminal(_) | Expr::Regex(_) => 0,
+            Expr::Rule(i) => self.rules[*i].depth.saturating_add(1),
+            Expr::Sequence(exprs) => exprs.iter().map(|e| self.depth(e)).max().unwrap_or(0),
+            Expr::Choice(exprs) => exprs.iter().map(|e| self.depth(e)).min().unwrap(),
+            Expr::Repeat { expr, min, .. } => {
+                if *min == 0 {
+                    0
+                } else {
+                    self.depth(expr)
+                }
+            }
+        }
+    }
+
+    fn expand(&self, expr: &Expr, budget: u32, rng: &mut Rng, s: &mut String) {
+        match expr {
+            Expr::Terminal(terminal) => s.push_str(terminal),
+            Expr::Regex(regex) => s.push_str(&regex.sample(rng)),
+            Expr::Rule(i) => self.expand(&self.rules[*i].expr, budget.saturating_sub(1), rng, s),
+            Expr::Sequence(exprs) => {
+                for expr in exprs {
+                    self.expand(expr, budget, rng, s);
+                }
+            }
+            Expr::Choice(exprs) => {
+                let depths = exprs.iter().map(|e| self.depth(e)).collect::<Vec<_>>();
+
+                // Out of budget, only the alternatives that finish soonest are left
+                let limit = if depths.iter().any(|&d| d <= budget) {
+                    budget
+                } else {
+                    *depths.iter().min().unwrap()
+                };
+
+                let fitting = (0..exprs.len())
+                    .filter(|&i| depths[i] <= limit)
+                    .collect::<Vec<_>>();
+
+                self.expand(
+                    &exprs[fitting[rng.gen_range(0..fitting.len())]],
+                    budget,
+                    rng,
+                    s,
+                );
+            }
+            Expr::Repeat { expr, min, max } => {
+                let max = if self.depth(expr) <= budget {
+                    (*max).min(min.saturating_add(self.max_repeat))
+                } else {
+                    *min
+                };
+
+                for _ in 0..rng.gen_range(*min..=max) {
+                    self.expand(expr, budget, rng, s);
+                }
+            }
+        }
+    }
+}
+
+impl Distribution<String> for &Grammar {
+    #[inline]
+    fn sample(&self, rng: &mut Rng) -> String {
+        Grammar::sample(self, rng)
+    }
+}
+
+impl Rng {
+    /// Returns a random sentence from `grammar`'s start rule.
+    #[inline]
+    pub fn gen_from_grammar(&mut self, grammar: &Grammar) -> String {
+        grammar.sample(self)
+    }
+}
+
+impl GrammarError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+
+    /// The line in the grammar source where parsing failed, starting at `1`.
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// What went wrong.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for GrammarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid grammar on line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for GrammarError {}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Name(String),
+    Terminal(String),
+    Regex(String),
+    Define,
+    Semicolon,
+    Symbol(char),
+}
+
+/// Splits the source into tokens, each with its line number.
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, GrammarError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    let mut line = 1;
+
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '\n' => {
+                line += 1;
+                continue;
+            }
+            c if c.is_whitespace() => continue,
+            '#' => {
+                while chars.next_if(|&c| c != '\n').is_some() {}
+                continue;
+            }
+            ':' if chars.next_if_eq(&':').is_some() && chars.next_if_eq(&'=').is_some() => {
+                Token::Define
+            }
+            ';' => Token::Semicolon,
+            '|' | '(' | ')' | '[' | ']' | '{' | '}' | '*' | '+' | '?' => Token::Symbol(c),
+            '"' | '\'' | '/' => {
+                let mut text = String::new();
+
+                loop {
+                    let Some(next) = chars.next() else {
+                        return Err(GrammarError::new(line, "unclosed terminal"));
+                    };
+
+                    match next {
+                        next if next == c => break,
+                        '\n' => return Err(GrammarError::new(line, "unclosed terminal")),
+                        // Regexes keep their escapes, apart from the escaped delimiter
+                        '\\' if c == '/' => match chars.next() {
+                            Some('/') => text.push('/'),
+                            Some(escaped) => {
+                                text.push('\\');
+                                text.push(escaped);
+                            }
+                            None => return Err(GrammarError::new(line, "unclosed terminal")),
+                        },
+                        '\\' => text.push(match chars.next() {
+                            Some('n') => '\n',
+                            Some('r') => '\r',
+                            Some('t') => '\t',
+                            Some(escaped) => escaped,
+                            None => return Err(GrammarError::new(line, "unclosed terminal")),
+                        }),
+                        next => text.push(next),
+                    }
+                }
+
+                if c == '/' {
+                    Token::Regex(text)
+                } else {
+                    Token::Terminal(text)
+                }
+            }
+            '<' => {
+                let mut name = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('>') => break,
+                        Some('\n') | None => {
+                            return Err(GrammarError::new(line, "unclosed `<`"));
+                        }
+                        Some(next) => name.push(next),
+                    }
+                }
+
+                Token::Name(name)
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut name = String::from(c);
+
+                while let Some(next) =
+                    chars.next_if(|&c| c.is_alphanumeric() || c == '_' || c == '-')
+                {
+                    name.push(next);
+                }
+
+                Token::Name(name)
+            }
+            c => {
+                return Err(GrammarError::new(
+                    line,
+                    format!("unexpected character `{c}`"),
+                ));
+            }
+        };
+
+        tokens.push((token, line));
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [(Token, usize)],
+    pos: usize,
+    names: Vec<String>,
+    /// Every rule referenced, with its line, to report undefined ones.
+    references: Vec<(String, usize)>,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: impl Into<String>) -> GrammarError {
+        let line = self
+            .tokens
+            .get(self.pos)
+            .or(self.tokens.last())
+            .map_or(1, |t| t.1);
+
+        GrammarError::new(line, message)
+    }
+
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.pos).map(|t| &t.0)
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let matches = self.peek() == Some(token);
+
+        if matches {
+            self.pos += 1;
+        }
+
+        matches
+    }
+
+    /// Whether the next tokens start another rule, ending the current one.
+    fn at_definition(&self) -> bool {
+        matches!(self.peek(), Some(Token::Name(_)))
+            && matches!(self.tokens.get(self.pos + 1), Some((Token::Define, _)))
+    }
+
+    fn parse_choice(&mut self) -> Result<Expr, GrammarError> {
+        let mut alternatives = vec![self.parse_sequence()?];
+
+        while self.eat(&Token::Symbol('|')) {
+            alternatives.push(self.parse_sequence()?);
+        }
+
+        Ok(if alternatives.len() == 1 {
+            alternatives.pop().unwrap()
+        } else {
+            Expr::Choice(alternatives)
+        })
+    }
+
+    fn parse_sequence(&mut self) -> Result<Expr, GrammarError> {
+        let mut exprs = Vec::new();
+
+        while let Some(expr) = self.parse_atom()? {
+            let (min, max) = match self.peek() {
+                Some(Token::Symbol('*')) => (0, u32::MAX),
+                Some(Token::Symbol('+')) => (1, u32::MAX),
+                Some(Token::Symbol('?')) => (0, 1),
+                _ => {
+                    exprs.push(expr);
+                    continue;
+                }
+            };
+
+            self.pos += 1;
+
+            exprs.push(Expr::Repeat {
+                expr: Box::new(expr),
+                min,
+                max,
+            });
+        }
+
+        if exprs.is_empty() {
+            return Err(self.error("expected an expression"));
+        }
+
+        Ok(if exprs.len() == 1 {
+            exprs.pop().unwrap()
+        } else {
+            Expr::Sequence(exprs)
+        })
+    }
+
+    /// Parses a terminal, rule name or bracketed group, or returns `None` at the end of
+    /// a sequence.
+    fn parse_atom(&mut self) -> Result<Option<Expr>, GrammarError> {
+        if self.at_definition() {
+            return Ok(None);
+        }
+
+        let Some((token, line)) = self.tokens.get(self.pos) else {
+            return Ok(None);
+        };
+
+        let (close, min, max) = match token {
+            Token::Name(name) => {
+                let index = self
+                    .names
+                    .iter()
+                    .position(|n| n == name)
+                    .unwrap_or(usize::MAX);
+
+                self.references.push((name.clone(), *line));
+                self.pos += 1;
+
+                return Ok(Some(Expr::Rule(index)));
+            }
+            Token::Terminal(terminal) => {
+                self.pos += 1;
+
+                return Ok(Some(Expr::Terminal(terminal.clone())));
+            }
+            Token::Regex(pattern) => {
+                let regex = Regex::new(pattern).map_err(|e| self.error(e.to_string()))?;
+                self.pos += 1;
+
+                return Ok(Some(Expr::Regex(regex)));
+            }
+            Token::Symbol('(') => (')', 1, 1),
+            Token::Symbol('[') => (']', 0, 1),
+            Token::Symbol('{') => ('}', 0, u32::MAX),
+            _ => return Ok(None),
+        };
+
+        self.pos += 1;
+
+        let inner = self.parse_choice()?;
+
+        if !self.eat(&Token::Symbol(close)) {
+            return Err(self.error(format!("expected `{close}`")));
+        }
+
+        Ok(Some(if min == 1 && max == 1 {
+            inner
+        } else {
+            Expr::Repeat {
+                expr: Box::new(inner),
+                min,
+                max,
+            }
+        }))
+    }
+}
+
+#[test]
+fn test_grammar() {
+    let grammar = Grammar::new(
+        r#"
+        # Arithmetic over small numbers
+        <expr> ::= term (("+" | "-") term)*
+        term   ::= number | "(" <expr> ")" | "-" term
+        number ::= /[1-9][0-9]{0,2}/ ;
+        "#,
+    )
+    .unwrap();
+
+    let rng = &mut Rng::from_seed(0);
+
+    for _ in 0..1000 {
+        let sentence = rng.gen_from_grammar(&grammar);
+
+        assert!(!sentence.is_empty());
+        assert!(
+            sentence
+                .chars()
+                .all(|c| c.is_ascii_digit() || "+-()".contains(c))
+        );
+
+        // Parentheses are balanced
+        let mut open = 0i32;
+
+        for c in sentence.chars() {
+            open += match c {
+                '(' => 1,
+                ')' => -1,
+                _ => 0,
+            };
+
+            assert!(open >= 0);
+        }
+
+        assert_eq!(open, 0);
+    }
+
+    // At depth 0, only the shortest derivations are used
+    let shallow = grammar.clone().with_max_depth(0).with_start("term");
+
+    for _ in 0..100 {
+        let number = shallow.sample(rng);
+
+        assert!(number.parse::<u32>().is_ok_and(|n| (1..1000).contains(&n)));
+        assert!(grammar.sample_rule(rng, "number").parse::<u32>().unwrap() > 0);
+    }
+
+    let list = Grammar::new("list ::= '[' [item {',' item}] ']'; item ::= 'x'").unwrap();
+
+    for sentence in list.sample_iter(rng).take(100) {
+        assert!(sentence == "[]" || (sentence.starts_with("[x") && sentence.ends_with("x]")));
+    }
+
+    for (source, line) in [
+        ("a ::= 'x'\nb ::= c", 2),
+        ("a ::= a 'x'", 1),
+        ("a ::= 'x'\n\na ::= 'y'", 3),
+        ("a ::= ('x'", 1),
+        ("'x'", 1),
+        ("a ::= 'x\n'", 1),
+    ] {
+        assert_eq!(Grammar::new(source).unwrap_err().line(), line, "{source:?}");
+    }
+}
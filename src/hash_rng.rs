@@ -0,0 +1,154 @@
+use crate::rng::{WYP0, WYP1, WYP2, wymix};
+use crate::{RandomRange, RandomValue, Rng};
+
+use core::ops::RangeBounds;
+
+/// Values that can be used as coordinates for [`hash_rng`] and [`RandomAccessRng`].
+///
+/// Implemented for all integers and for tuples and arrays of them.
+pub trait HashKey {
+    /// Mixes `self` into the running hash `h`.
+    fn mix_into(self, h: u64) -> u64;
+}
+
+macro_rules! impl_hash_key_int {
+    ($($t:ty),*) => {
+        $(
+            impl HashKey for $t {
+                #[inline]
+                fn mix_into(self, h: u64) -> u64 {
+                    // Sign-extend so that e.g. `-1i32` and `-1i64` hash the same
+                    wymix(h ^ WYP1, (self as i128 as u64) ^ WYP2)
+                }
+            }
+        )*
+    };
+}
+
+impl_hash_key_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl HashKey for u128 {
+    #[inline]
+    fn mix_into(self, h: u64) -> u64 {
+        ((self >> 64) as u64).mix_into((self as u64).mix_into(h))
+    }
+}
+
+impl HashKey for i128 {
+    #[inline]
+    fn mix_into(self, h: u64) -> u64 {
+        (self as u128).mix_into(h)
+    }
+}
+
+impl<T: HashKey, const N: usize> HashKey for [T; N] {
+    #[inline]
+    fn mix_into(self, h: u64) -> u64 {
+        self.into_iter().fold(h, |h, key| key.mix_into(h))
+    }
+}
+
+macro_rules! impl_hash_key_tuple {
+    ($($name:ident),*) => {
+        impl<$($name: HashKey),*> HashKey for ($($name,)*) {
+            #[inline]
+            #[allow(non_snake_case)]
+            fn mix_into(self, h: u64) -> u64 {
+                let ($($name,)*) = self;
+                $(let h = $name.mix_into(h);)*
+                h
+            }
+        }
+    };
+}
+
+impl_hash_key_tuple!(A);
+impl_hash_key_tuple!(A, B);
+impl_hash_key_tuple!(A, B, C);
+impl_hash_key_tuple!(A, B, C, D);
+impl_hash_key_tuple!(A, B, C, D, E);
+impl_hash_key_tuple!(A, B, C, D, E, F);
+
+/// Returns a random `u64` for the given coordinates, without any generator state.
+///
+/// The same `seed` and `coords` always give the same value, so the randomness of any
+/// cell can be computed independently and in any order.
+#[inline]
+pub fn hash_rng(seed: u64, coords: impl HashKey) -> u64 {
+    let h = coords.mix_into(seed ^ WYP0);
+
+    // Final avalanche so that neighboring coordinates are uncorrelated
+    wymix(h ^ WYP0, h ^ WYP1)
+}
+
+/// A stateless, counter-based generator: every index maps to its own random values.
+///
+/// Unlike [`Rng`], values don't depend on how many were generated before, so they can be
+/// looked up in any order and from multiple threads.
+#[derive(Copy, Clone, Debug)]
+pub struct RandomAccessRng {
+    seed: u64,
+}
+
+impl RandomAccessRng {
+    #[inline]
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// Returns the random `u64` at `index`.
+    #[inline]
+    pub fn at(&self, index: impl HashKey) -> u64 {
+        hash_rng(self.seed, index)
+    }
+
+    /// Returns an [`Rng`] seeded for `index`, for drawing several values at one location.
+    #[inline]
+    pub fn rng_at(&self, index: impl HashKey) -> Rng {
+        Rng::from_seed(self.at(index))
+    }
+
+    /// Returns the same value as `rng_at(index).gen_value()`.
+    #[inline]
+    pub fn gen_value_at<T: RandomValue>(&self, index: impl HashKey) -> T {
+        self.rng_at(index).gen_value()
+    }
+
+    /// Returns the same value as `rng_at(index).gen_range(range)`.
+    #[inline]
+    pub fn gen_range_at<T: RandomRange>(
+        &self,
+        index: impl HashKey,
+        range: impl RangeBounds<T>,
+    ) -> T {
+        self.rng_at(index).gen_range(range)
+    }
+}
+
+#[test]
+fn test_hash_rng() {
+    let world = RandomAccessRng::new(1234);
+
+    assert_eq!(world.at((1, 2, 3)), world.at((1, 2, 3)));
+    assert_eq!(world.at((1, 2, 3)), hash_rng(1234, (1, 2, 3)));
+    assert_eq!(world.at([1, 2, 3]), world.at((1i64, 2u8, 3i16)));
+    assert_ne!(world.at((1, 2, 3)), world.at((1, 3, 2)));
+    assert_ne!(
+        world.at((1, 2, 3)),
+        RandomAccessRng::new(1235).at((1, 2, 3))
+    );
+
+    assert_eq!(
+        world.gen_range_at((5, 5), 0..100),
+        world.rng_at((5, 5)).gen_range(0..100)
+    );
+
+    // Neighboring cells should still flip about half of the bits
+    let mut flipped = 0;
+
+    for x in 0..1000 {
+        flipped += (world.at(x) ^ world.at(x + 1)).count_ones();
+    }
+
+    assert!((30_000..34_000).contains(&flipped));
+}
@@ -0,0 +1,176 @@
+use crate::{Distribution, RandomRange, RandomValue, Rng};
+
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ops::{Bound, RangeBounds};
+
+impl Rng {
+    /// Returns an infinite iterator of random values, borrowing the generator.
+    #[inline]
+    pub fn values<T: RandomValue>(&mut self) -> Values<'_, T> {
+        Values {
+            rng: self,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns an infinite iterator of random values in `range`, borrowing the generator.
+    ///
+    /// # Panics
+    /// When iterated, if the range is empty.
+    #[inline]
+    pub fn range_iter<T: RandomRange + Copy>(
+        &mut self,
+        range: impl RangeBounds<T>,
+    ) -> RangeIter<'_, T> {
+        RangeIter {
+            rng: self,
+            start: range.start_bound().cloned(),
+            end: range.end_bound().cloned(),
+        }
+    }
+
+    /// Returns an infinite iterator of booleans that are `true` with the given probability,
+    /// borrowing the generator.
+    #[inline]
+    pub fn bools(&mut self, probability: f64) -> Bools<'_> {
+        Bools {
+            rng: self,
+            probability,
+        }
+    }
+}
+
+/// Infinite iterator of random values, created by [`Rng::values`].
+#[derive(Debug)]
+pub struct Values<'a, T> {
+    rng: &'a mut Rng,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: RandomValue> Iterator for Values<'_, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        Some(self.rng.gen_value())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<T: RandomValue> FusedIterator for Values<'_, T> {}
+
+/// Infinite iterator of random values in a range, created by [`Rng::range_iter`].
+#[derive(Debug)]
+pub struct RangeIter<'a, T> {
+    rng: &'a mut Rng,
+    start: Bound<T>,
+    end: Bound<T>,
+}
+
+impl<T: RandomRange + Copy> Iterator for RangeIter<'_, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        Some(self.rng.gen_range((self.start, self.end)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<T: RandomRange + Copy> FusedIterator for RangeIter<'_, T> {}
+
+/// Infinite iterator of random booleans, created by [`Rng::bools`].
+#[derive(Debug)]
+pub struct Bools<'a> {
+    rng: &'a mut Rng,
+    probability: f64,
+}
+
+impl Iterator for Bools<'_> {
+    type Item = bool;
+
+    #[inline]
+    fn next(&mut self) -> Option<bool> {
+        Some(self.rng.gen_bool(self.probability))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl FusedIterator for Bools<'_> {}
+
+/// Infinite iterator of samples from a distribution, created by
+/// [`Distribution::sample_iter`].
+#[derive(Debug)]
+pub struct SampleIter<'a, D, T> {
+    distribution: D,
+    rng: &'a mut Rng,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, D, T> SampleIter<'a, D, T> {
+    #[inline]
+    pub(crate) fn new(distribution: D, rng: &'a mut Rng) -> Self {
+        Self {
+            distribution,
+            rng,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<D: Distribution<T>, T> Iterator for SampleIter<'_, D, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        Some(self.distribution.sample(self.rng))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<D: Distribution<T>, T> FusedIterator for SampleIter<'_, D, T> {}
+
+#[test]
+fn test_iter() {
+    let rng = &mut Rng::from_seed(0);
+
+    let rolls: Vec<u32> = rng.range_iter(1..=6).take(100).collect();
+    assert_eq!(rolls.len(), 100);
+    assert!(rolls.iter().all(|r| (1..=6).contains(r)));
+
+    // Iterators draw from the borrowed generator, in order
+    let a: Vec<u64> = Rng::from_seed(5).values().take(10).collect();
+    let b: Vec<u64> = {
+        let rng = &mut Rng::from_seed(5);
+        (0..10).map(|_| rng.gen_value()).collect()
+    };
+    assert_eq!(a, b);
+
+    assert!(rng.bools(1.0).take(100).all(|b| b));
+    assert!(!rng.bools(0.0).take(100).any(|b| b));
+
+    let sampler = crate::CdfSampler::new(vec![('a', 1.0), ('b', 0.0), ('c', 1.0)]);
+    assert!(
+        sampler
+            .sample_iter(rng)
+            .take(100)
+            .all(|&c| c == 'a' || c == 'c')
+    );
+}
@@ -1,35 +1,101 @@
 #![allow(missing_docs)]
 #![allow(dead_code)]
 
-// #[cfg(feature = "fuzz")]
 mod enum_traits;
+mod fill;
+#[cfg(feature = "fuzz")]
 mod fuzz;
 mod gen_range;
+mod gen_regex;
+mod gen_string;
 mod gen_value;
+mod geometry;
+mod grammar;
+mod hash_rng;
+mod iter;
+mod noise;
+mod poisson_disk;
 mod rng;
 mod sample;
 mod shuffle;
+mod stats;
+#[cfg(test)]
+mod test_vectors;
 
 pub use rng_derive::*;
 
 pub use enum_traits::*;
+#[cfg(feature = "fuzz")]
 pub use fuzz::*;
 pub use gen_range::*;
+pub use gen_regex::*;
+pub use gen_string::*;
 pub use gen_value::*;
+pub use geometry::*;
+pub use grammar::*;
+pub use hash_rng::*;
+pub use iter::*;
+pub use noise::*;
+pub use poisson_disk::*;
 pub use rng::*;
 pub use sample::*;
 pub use shuffle::*;
+pub use stats::*;
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::Rng;
 
+    /// Asserts that two values have the same debug output. Generated floats can be NaN, which
+    /// never equals itself, so instances holding them can't be compared with `==`.
+    #[cfg(feature = "fuzz")]
+    #[track_caller]
+    fn assert_same_debug<T: core::fmt::Debug>(a: &T, b: &T) {
+        assert_eq!(format!("{a:?}"), format!("{b:?}"));
+    }
+
     #[test]
-    fn test_range_int() {}
+    fn test_range_int() {
+        let rng = &mut Rng::from_seed(0);
+
+        for span in [2, 6, 7, 100] {
+            let mut counts = vec![0; span];
+
+            for _ in 0..span * 1000 {
+                counts[rng.gen_range(0..span)] += 1;
+            }
+
+            let result = chi_squared_uniform(&counts);
+            assert!(result.passed(0.001), "0..{span}: {result:?}");
+        }
+
+        let mut counts = [0; 21];
+
+        for _ in 0..21_000 {
+            counts[(rng.gen_range(-10i8..=10) + 10) as usize] += 1;
+        }
+
+        assert!(chi_squared_uniform(&counts).passed(0.001));
+    }
 
     #[test]
-    fn test_range_float() {}
+    fn test_range_float() {
+        let rng = &mut Rng::from_seed(0);
+
+        let samples = (0..10_000)
+            .map(|_| (rng.gen_range(-3.0..5.0) + 3.0) / 8.0)
+            .collect::<Vec<f64>>();
+
+        assert!(kolmogorov_smirnov(&samples).passed(0.001));
+        assert!(serial_correlation(&samples).passed(0.001));
+
+        let samples = (0..10_000)
+            .map(|_| rng.gen_range(10.0f32..20.0) as f64 / 10.0 - 1.0)
+            .collect::<Vec<_>>();
+
+        assert!(kolmogorov_smirnov(&samples).passed(0.001));
+    }
 
     #[test]
     fn test_gen() {
@@ -39,18 +105,62 @@ mod tests {
 
             dbg!(rng.gen_value::<f32>());
         }
+
+        let rng = &mut Rng::from_seed(0);
+
+        for result in quality_suite(|| rng.gen_value()) {
+            assert!(result.passed(0.001), "{result:?}");
+        }
+
+        let mut counts = [0; 256];
+
+        for _ in 0..256 * 100 {
+            counts[rng.gen_value::<u8>() as usize] += 1;
+        }
+
+        assert!(chi_squared_uniform(&counts).passed(0.001));
+
+        let floats = (0..10_000)
+            .map(|_| rng.gen_value::<f32>() as f64)
+            .collect::<Vec<_>>();
+
+        assert!(kolmogorov_smirnov(&floats).passed(0.001));
     }
 
     #[test]
-    fn test_sample() {}
+    fn test_sample() {
+        let rng = &mut Rng::from_seed(0);
+        let slice = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+        let mut counts = [0; 10];
+
+        for _ in 0..10_000 {
+            counts[*slice.sample(rng).unwrap()] += 1;
+        }
+
+        assert!(chi_squared_uniform(&counts).passed(0.001));
+
+        let weights = [1.0, 2.0, 0.0, 4.0, 8.0];
+        let sampler = CdfSampler::new(weights.iter().copied().enumerate().collect());
+
+        let mut counts = [0; 5];
+
+        for _ in 0..15_000 {
+            counts[*sampler.sample(rng)] += 1;
+        }
+
+        let probabilities = weights.map(f64::from);
+        assert!(chi_squared(&counts, &probabilities).passed(0.001));
+        assert_eq!(counts[2], 0);
+    }
 
     #[test]
     fn test_sample_multi() {
-        let mut rng = Rng::new();
+        let rng = Rng::new();
         let slice = [1, 2, 3, 4, 5];
 
         // Test basic functionality
-        let samples = slice.sample_multi(&mut rng, 3);
+        let samples = slice.sample_multi(rng, 3);
 
         assert_eq!(samples.len(), 3);
 
@@ -67,7 +177,7 @@ mod tests {
         );
 
         // Test requesting all elements (no duplicates, full set)
-        let all_samples = slice.sample_multi(&mut rng, slice.len());
+        let all_samples = slice.sample_multi(rng, slice.len());
 
         assert_eq!(all_samples.len(), slice.len());
 
@@ -82,71 +192,533 @@ mod tests {
 
         // Test edge cases
         assert!(Vec::<&i32>::new().is_empty()); // Empty slice
-        assert!([1, 2, 3].sample_multi(&mut rng, 0).is_empty()); // amount = 0
-    }
-
-    // #[test]
-    // fn test_random_instance_struct_simple() {
-    //     #[derive(Debug, RandomInstance)]
-    //     struct TestStruct {
-    //         int: u32,
-    //         float: f64,
-    //         b: bool,
-    //     }
-
-    //     for _ in 0..4 {
-    //         dbg!(TestStruct::random_instance(Rng::new()));
-    //     }
-    // }
-
-    // #[test]
-    // fn test_random_instance_enum() {
-    //     #[derive(Debug, RandomInstance)]
-    //     enum TestEnum {
-    //         One,
-    //         Two(f32),
-    //         Three { b: bool },
-    //     }
-
-    //     for _ in 0..4 {
-    //         dbg!(TestEnum::random_instance(Rng::new()));
-    //     }
-    // }
-
-    // #[test]
-    // fn test_random_instance_nested() {
-    //     #[derive(Debug, RandomInstance)]
-    //     struct TestStruct {
-    //         int: u32,
-    //         float: f64,
-    //         b: bool,
-    //         inner_struct: InnerStruct,
-    //         inner_enum: InnerEnum,
-    //     }
-
-    //     #[derive(Debug, RandomInstance)]
-    //     struct InnerStruct {
-    //         int: u32,
-    //         float: f64,
-    //         b: bool,
-    //     }
-
-    //     #[derive(Debug, RandomInstance)]
-    //     enum InnerEnum {
-    //         One,
-    //         Two(f32),
-    //         Three { b: bool },
-    //     }
-
-    //     for _ in 0..4 {
-    //         dbg!(TestStruct::random_instance(Rng::new()));
-    //     }
-    // }
-
-    // #[test]
-    // fn test_random_instance_f32() {
-    //     for _ in 0..100 {
-    //         dbg!(f32::random_instance(Rng::new()));
-    //     }
-    // }
+        assert!([1, 2, 3].sample_multi(rng, 0).is_empty()); // amount = 0
+    }
+
+    #[test]
+    fn test_sample_multi_uniform() {
+        // Every element is equally likely in every position
+        let rng = &mut Rng::from_seed(0);
+        let slice = [0, 1, 2, 3, 4];
+
+        let mut counts = [[0; 5]; 3];
+
+        for _ in 0..5000 {
+            for (position, &&value) in slice.sample_multi(rng, 3).iter().enumerate() {
+                counts[position][value] += 1;
+            }
+        }
+
+        for counts in counts {
+            assert!(chi_squared_uniform(&counts).passed(0.001));
+        }
+    }
+
+    #[cfg(feature = "fuzz")]
+    #[test]
+    fn test_random_instance_struct_simple() {
+        #[derive(Debug, RandomInstance)]
+        struct TestStruct {
+            int: u32,
+            float: f64,
+            b: bool,
+        }
+
+        for seed in 0..16 {
+            let a = TestStruct::random_instance(&mut Rng::from_seed(seed));
+            let b = TestStruct::random_instance(&mut Rng::from_seed(seed));
+
+            assert_same_debug(&a, &b);
+        }
+
+        let rng = &mut Rng::from_seed(0);
+        let instances = (0..64)
+            .map(|_| TestStruct::random_instance(rng))
+            .collect::<Vec<_>>();
+
+        // Every field is generated rather than left at a fixed value
+        assert!(instances.iter().any(|s| s.b) && instances.iter().any(|s| !s.b));
+        assert!(instances.iter().any(|s| s.int != instances[0].int));
+        assert!(
+            instances
+                .iter()
+                .any(|s| s.float.to_bits() != instances[0].float.to_bits())
+        );
+    }
+
+    #[cfg(feature = "fuzz")]
+    #[test]
+    fn test_random_instance_enum() {
+        #[derive(Debug, RandomInstance)]
+        enum TestEnum {
+            One,
+            Two(f32),
+            Three { b: bool },
+        }
+
+        // Every variant gets picked
+        let rng = &mut Rng::from_seed(0);
+        let mut seen = [false; 3];
+
+        for _ in 0..64 {
+            seen[match TestEnum::random_instance(rng) {
+                TestEnum::One => 0,
+                TestEnum::Two(_) => 1,
+                TestEnum::Three { .. } => 2,
+            }] = true;
+        }
+
+        assert_eq!(seen, [true; 3]);
+    }
+
+    #[cfg(feature = "fuzz")]
+    #[test]
+    fn test_random_instance_nested() {
+        #[derive(Debug, RandomInstance)]
+        struct TestStruct {
+            int: u32,
+            inner_struct: InnerStruct,
+            inner_enum: InnerEnum,
+        }
+
+        #[derive(Debug, RandomInstance)]
+        struct InnerStruct {
+            int: u32,
+            b: bool,
+            values: Vec<u8>,
+        }
+
+        #[derive(Debug, RandomInstance)]
+        enum InnerEnum {
+            One,
+            Two(u16),
+            Three { b: bool },
+        }
+
+        let rng = &mut Rng::from_seed(2);
+        let instances = (0..256)
+            .map(|_| TestStruct::random_instance(rng))
+            .collect::<Vec<_>>();
+
+        // Nested fields are filled in from the same rng, not defaulted
+        assert!(instances.iter().any(|s| s.inner_struct.int != 0));
+        assert!(instances.iter().any(|s| s.inner_struct.b));
+        assert!(instances.iter().any(|s| !s.inner_struct.values.is_empty()));
+        assert!(
+            instances
+                .iter()
+                .any(|s| s.inner_struct.int != s.int && s.inner_struct.int != 0)
+        );
+        assert!(
+            instances
+                .iter()
+                .any(|s| matches!(s.inner_enum, InnerEnum::One))
+        );
+        assert!(
+            instances
+                .iter()
+                .any(|s| matches!(s.inner_enum, InnerEnum::Two(n) if n != 0))
+        );
+        assert!(
+            instances
+                .iter()
+                .any(|s| matches!(s.inner_enum, InnerEnum::Three { b: true }))
+        );
+
+        // Plain struct fields don't add a nesting level, so collections keep the full size
+        assert!(
+            instances
+                .iter()
+                .all(|s| s.inner_struct.values.len() <= DEFAULT_SIZE)
+        );
+    }
+
+    #[cfg(feature = "fuzz")]
+    #[test]
+    fn test_random_instance_seeded() {
+        #[derive(Debug, PartialEq, RandomInstance)]
+        struct Tuple(u8, Vec<u8>, Option<i64>);
+
+        #[derive(Debug, PartialEq, RandomInstance)]
+        struct Generic<T, U>
+        where
+            U: Clone,
+        {
+            value: T,
+            values: Vec<U>,
+            tuple: Tuple,
+        }
+
+        #[derive(Debug, PartialEq, RandomInstance)]
+        struct Unit;
+
+        // The same seed gives the same instance
+        for seed in 0..256 {
+            let a = Generic::<f32, (u8, bool)>::random_instance(&mut Rng::from_seed(seed));
+            let b = Generic::<f32, (u8, bool)>::random_instance(&mut Rng::from_seed(seed));
+
+            assert_same_debug(&a, &b);
+        }
+
+        assert_ne!(
+            Tuple::random_instance(&mut Rng::from_seed(1)),
+            Tuple::random_instance(&mut Rng::from_seed(2))
+        );
+        assert_eq!(Unit::random_instance(Rng::new()), Unit);
+    }
+
+    #[cfg(feature = "fuzz")]
+    #[test]
+    fn test_shrink_derive() {
+        #[derive(Debug, Clone, PartialEq, RandomInstance, Shrink)]
+        struct Point(i32, i32);
+
+        #[derive(Debug, Clone, PartialEq, RandomInstance, Shrink)]
+        enum Shape {
+            Empty,
+            Circle { center: Point, radius: f64 },
+            Polygon(Vec<Point>),
+        }
+
+        // Fails for any polygon with a point far to the right
+        let fails =
+            |s: &Shape| matches!(s, Shape::Polygon(points) if points.iter().any(|p| p.0 > 10));
+
+        let input = Shape::Polygon(vec![Point(-5, 3), Point(500, -200), Point(7, 7)]);
+        assert_eq!(minimize(input, fails), Shape::Polygon(vec![Point(11, 0)]));
+
+        // Unit variants declared earlier are tried first
+        let input = Shape::Circle {
+            center: Point(1, 2),
+            radius: 3.5,
+        };
+        assert_eq!(minimize(input, |_| true), Shape::Empty);
+
+        // Random failing inputs all shrink to the same minimal counterexample
+        let rng = &mut Rng::from_seed(0);
+
+        for _ in 0..16 {
+            let input = Vec::<(u8, bool)>::random_instance(rng);
+
+            if input.iter().filter(|(_, b)| *b).count() >= 2 {
+                assert_eq!(
+                    minimize(input, |v| v.iter().filter(|(_, b)| *b).count() >= 2),
+                    vec![(0, true), (0, true)]
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "fuzz")]
+    #[test]
+    fn test_random_instance_f32() {
+        let floats = (0..1000)
+            .map(|_| f32::random_instance(&mut Rng::from_seed(4)))
+            .collect::<Vec<_>>();
+
+        // The same seed gives the same bits, NaN included
+        assert!(floats.iter().all(|x| x.to_bits() == floats[0].to_bits()));
+
+        let rng = &mut Rng::from_seed(4);
+        let floats = (0..1000)
+            .map(|_| f32::random_instance(rng))
+            .collect::<Vec<_>>();
+
+        assert!(floats.iter().any(|x| x.is_finite() && x.abs() > 1.0));
+        assert!(floats.iter().any(|x| x.is_finite() && x.fract() != 0.0));
+        assert!(floats.iter().any(|x| x.is_sign_negative()));
+    }
+
+    #[cfg(feature = "fuzz")]
+    #[test]
+    fn test_random_instance_recursive() {
+        use std::rc::Rc;
+
+        #[derive(Debug, Clone, RandomInstance, Shrink)]
+        enum Expr {
+            Lit(i32),
+            Neg(Box<Expr>),
+            Add(Box<Expr>, Rc<Self>),
+            Var,
+        }
+
+        fn depth(e: &Expr) -> usize {
+            match e {
+                Expr::Lit(_) | Expr::Var => 1,
+                Expr::Neg(a) => 1 + depth(a),
+                Expr::Add(a, b) => 1 + depth(a).max(depth(b)),
+            }
+        }
+
+        #[derive(Debug, RandomInstance)]
+        struct Tree {
+            children: Vec<Tree>,
+            next: Option<Box<Tree>>,
+        }
+
+        let rng = &mut Rng::from_seed(3);
+        let mut max_depth = 0;
+
+        for _ in 0..1000 {
+            let e = Expr::random_instance(rng);
+            max_depth = max_depth.max(depth(&e));
+
+            assert!(depth(&e) <= DEFAULT_MAX_DEPTH + 1);
+        }
+
+        assert!(max_depth > 2);
+
+        // Two optional children would recurse without bound if depth didn't end them
+        #[derive(Debug, RandomInstance)]
+        struct Bin {
+            l: Option<Box<Bin>>,
+            r: Option<Box<Bin>>,
+            v: u8,
+        }
+
+        fn height(b: &Bin) -> usize {
+            let child = |c: &Option<Box<Bin>>| c.as_deref().map_or(0, height);
+
+            1 + child(&b.l).max(child(&b.r))
+        }
+
+        for _ in 0..100 {
+            Tree::random_instance(rng);
+        }
+
+        let rng = &mut Rng::from_seed(1);
+
+        for _ in 0..20_000 {
+            assert!(height(&Bin::random_instance(rng)) <= DEFAULT_MAX_DEPTH + 1);
+        }
+
+        // Mutually recursive types never name themselves, the pointer between them ends them
+        #[derive(Debug, RandomInstance)]
+        enum Stmt {
+            Nop(u8),
+            Call(Box<Block>),
+            Loop(Box<Block>),
+        }
+
+        #[derive(Debug, RandomInstance)]
+        struct Block {
+            a: Stmt,
+            b: Stmt,
+        }
+
+        fn stmt_depth(s: &Stmt) -> usize {
+            match s {
+                Stmt::Nop(_) => 1,
+                Stmt::Call(b) | Stmt::Loop(b) => 1 + stmt_depth(&b.a).max(stmt_depth(&b.b)),
+            }
+        }
+
+        let rng = &mut Rng::from_seed(0);
+
+        for _ in 0..1000 {
+            assert!(stmt_depth(&Stmt::random_instance(rng)) <= DEFAULT_MAX_DEPTH + 1);
+        }
+
+        let e = Expr::Neg(Box::new(Expr::Lit(5)));
+        assert!(matches!(minimize(e, |_| true), Expr::Neg(a) if matches!(*a, Expr::Lit(0))));
+    }
+
+    #[cfg(feature = "fuzz")]
+    #[test]
+    fn test_random_instance_edge_cases() {
+        let rng = &mut Rng::from_seed(11);
+        let ctx = &mut GenContext::new(rng);
+
+        let ints = (0..10_000)
+            .map(|_| i32::random_instance_with(ctx))
+            .collect::<Vec<_>>();
+
+        for special in [0, 1, 2, i32::MIN, i32::MAX, 1 << 20] {
+            assert!(ints.contains(&special), "{special} was never generated");
+        }
+
+        let floats = (0..10_000)
+            .map(|_| f64::random_instance_with(ctx))
+            .collect::<Vec<_>>();
+
+        assert!(floats.iter().any(|x| x.is_nan()));
+        assert!(
+            floats
+                .iter()
+                .any(|x| x.is_infinite() && x.is_sign_negative())
+        );
+        assert!(floats.iter().any(|x| *x == 0.0 && x.is_sign_negative()));
+        assert!(floats.iter().any(|x| x.is_subnormal()));
+        assert!(floats.iter().any(|x| x.is_normal() && *x < -1.0));
+
+        // Without edge cases, integers are uniform and small values are rare
+        let ctx = &mut GenContext::new(rng).with_edge_case_probability(0.0);
+
+        assert!((0..1000).all(|_| u64::random_instance_with(ctx) > 1 << 32));
+    }
+
+    #[cfg(feature = "fuzz")]
+    #[test]
+    fn test_random_instance_collections() {
+        use std::cell::{Cell, RefCell};
+        use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList};
+        use std::path::PathBuf;
+
+        let rng = &mut Rng::from_seed(5);
+        let ctx = &mut GenContext::new(rng).with_size(32);
+
+        for _ in 0..100 {
+            // Sets can't hold more elements than the type has values
+            assert!(HashSet::<bool>::random_instance_with(ctx).len() <= 2);
+            assert!(BTreeSet::<u8>::random_instance_with(ctx).len() <= 32);
+
+            let map = HashMap::<u16, String>::random_instance_with(ctx);
+            let tree = BTreeMap::<(), u8>::random_instance_with(ctx);
+            assert!(map.len() <= 32 && tree.len() <= 1);
+
+            let heap = BinaryHeap::<i8>::random_instance_with(ctx).into_sorted_vec();
+            assert!(heap.is_sorted());
+
+            LinkedList::<Result<u8, char>>::random_instance_with(ctx);
+            <(u8, i8, u16, i16, u32, i32, u64, i64, bool, char, (), f32)>::random_instance_with(
+                ctx,
+            );
+            <(Cell<u8>, RefCell<Vec<u8>>, std::sync::Arc<u8>)>::random_instance_with(ctx);
+
+            let path = PathBuf::random_instance_with(ctx);
+            assert!(path.components().count() <= 9);
+        }
+
+        let set = (0..50).collect::<HashSet<u32>>();
+        assert_eq!(minimize(set, |s| s.contains(&7)), HashSet::from([7]));
+
+        let path = PathBuf::from("a/b/c/d");
+        assert_eq!(minimize(path, |p| p.ends_with("c")), PathBuf::from("c"));
+
+        let tuple = (
+            1u8, 2u8, 3u8, 4u8, 5u8, 6u8, 7u8, 8u8, 9u8, 10u8, 11u8, 12u8,
+        );
+        assert_eq!(
+            minimize(tuple, |t| t.11 > 5),
+            (0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 6)
+        );
+    }
+
+    #[cfg(feature = "fuzz")]
+    #[test]
+    fn test_random_instance_attributes() {
+        fn gen_email(ctx: &mut GenContext<'_>) -> String {
+            let user = ctx.rng().gen_string(Alphabet::Lowercase, 1..=8);
+
+            format!("{user}@example.com")
+        }
+
+        fn is_adult(person: &Person) -> bool {
+            person.age >= 18
+        }
+
+        #[derive(Debug, Clone, RandomInstance, Shrink)]
+        #[fuzz(filter = is_adult)]
+        struct Person {
+            #[fuzz(range = 0..=120)]
+            age: u8,
+            #[fuzz(len = 1..=16)]
+            name: String,
+            #[fuzz(len = ..10)]
+            tags: Vec<u32>,
+            #[fuzz(with = gen_email)]
+            email: String,
+            #[fuzz(default)]
+            id: Option<u64>,
+        }
+
+        #[derive(Debug, Clone, PartialEq, RandomInstance, Shrink)]
+        enum Status {
+            #[fuzz(weight = 0)]
+            Unknown,
+            Active,
+            #[fuzz(weight = 3)]
+            Banned(#[fuzz(range = 1..=30)] u8),
+        }
+
+        let rng = &mut Rng::from_seed(9);
+
+        for _ in 0..1000 {
+            let person = Person::random_instance(rng);
+
+            assert!((18..=120).contains(&person.age));
+            assert!((1..=16).contains(&person.name.chars().count()));
+            assert!(person.tags.len() < 10);
+            assert!(person.email.ends_with("@example.com"));
+            assert_eq!(person.id, None);
+        }
+
+        let statuses = (0..4000)
+            .map(|_| Status::random_instance(rng))
+            .collect::<Vec<_>>();
+
+        let banned = statuses
+            .iter()
+            .filter(|s| matches!(s, Status::Banned(_)))
+            .count();
+
+        assert!(!statuses.contains(&Status::Unknown));
+        assert!((2800..3200).contains(&banned));
+        assert!(statuses.iter().all(|s| match s {
+            Status::Banned(days) => (1..=30).contains(days),
+            _ => true,
+        }));
+
+        // Shrinking keeps to the attributes
+        let person = Person::random_instance(rng);
+        let minimal = minimize(person, |_| true);
+
+        assert_eq!(minimal.age, 18);
+        assert_eq!(minimal.name.chars().count(), 1);
+        assert!(minimal.tags.is_empty());
+
+        assert_eq!(minimize(Status::Banned(20), |_| true), Status::Active);
+        assert_eq!(
+            minimize(Status::Banned(20), |s| matches!(s, Status::Banned(_))),
+            Status::Banned(1)
+        );
+    }
+
+    #[cfg(feature = "fuzz")]
+    #[test]
+    fn test_random_instance_patterns() {
+        const QUERY: &str = r#"
+            query ::= "SELECT " column {", " column} " FROM t" [" WHERE " column " = " /\d+/]
+            column ::= "a" | "b" | "c"
+        "#;
+
+        #[derive(Debug, Clone, RandomInstance, Shrink)]
+        struct Request {
+            #[fuzz(regex = "[a-z]{3,8}@example\\.com")]
+            email: String,
+            #[fuzz(grammar = QUERY)]
+            query: Box<str>,
+            id: u8,
+        }
+
+        let rng = &mut Rng::from_seed(4);
+
+        for _ in 0..1000 {
+            let request = Request::random_instance(rng);
+
+            let (user, domain) = request.email.split_once('@').unwrap();
+            assert!((3..=8).contains(&user.len()) && domain == "example.com");
+
+            assert!(request.query.starts_with("SELECT "));
+            assert!(request.query.contains(" FROM t"));
+        }
+
+        // Pattern fields are kept as they are while the others shrink
+        let request = Request::random_instance(rng);
+        let minimal = minimize(request.clone(), |_| true);
+
+        assert_eq!(minimal.email, request.email);
+        assert_eq!(minimal.query, request.query);
+        assert_eq!(minimal.id, 0);
+    }
 }
@@ -0,0 +1,134 @@
+use crate::noise::NoiseFn;
+
+/// Shifts each octave's sample position so octaves don't line up at the origin.
+const OCTAVE_OFFSET: f64 = 31.416;
+
+macro_rules! impl_fractal {
+    ($($t:ident),*) => {
+        $(
+            impl<S> $t<S> {
+                /// Wraps `source` with 6 octaves, a lacunarity of `2.0` and a gain of `0.5`.
+                #[inline]
+                pub fn new(source: S) -> Self {
+                    Self {
+                        source,
+                        octaves: 6,
+                        lacunarity: 2.0,
+                        gain: 0.5,
+                    }
+                }
+
+                /// Sets the number of layered octaves.
+                #[inline]
+                #[must_use]
+                pub fn with_octaves(mut self, octaves: u32) -> Self {
+                    self.octaves = octaves;
+                    self
+                }
+
+                /// Sets the frequency multiplier between octaves.
+                #[inline]
+                #[must_use]
+                pub fn with_lacunarity(mut self, lacunarity: f64) -> Self {
+                    self.lacunarity = lacunarity;
+                    self
+                }
+
+                /// Sets the amplitude multiplier between octaves.
+                #[inline]
+                #[must_use]
+                pub fn with_gain(mut self, gain: f64) -> Self {
+                    self.gain = gain;
+                    self
+                }
+
+                /// Sums `f(sample)` over all octaves, normalized by the total amplitude.
+                #[inline]
+                fn accumulate<const N: usize>(
+                    &self,
+                    point: [f64; N],
+                    f: impl Fn(f64) -> f64,
+                ) -> f64
+                where
+                    S: NoiseFn<N>,
+                {
+                    let mut frequency = 1.0;
+                    let mut amplitude = 1.0;
+                    let mut total = 0.0;
+                    let mut amplitude_sum = 0.0;
+
+                    for octave in 0..self.octaves {
+                        let shift = octave as f64 * OCTAVE_OFFSET;
+                        let p = point.map(|x| x.mul_add(frequency, shift));
+
+                        total += f(self.source.get(p)) * amplitude;
+                        amplitude_sum += amplitude;
+
+                        frequency *= self.lacunarity;
+                        amplitude *= self.gain;
+                    }
+
+                    if amplitude_sum > 0.0 {
+                        total / amplitude_sum
+                    } else {
+                        0.0
+                    }
+                }
+            }
+        )*
+    };
+}
+
+/// Fractal Brownian motion: layers octaves of a noise at increasing frequency and
+/// decreasing amplitude. Keeps the range of the source.
+#[derive(Clone, Debug)]
+pub struct Fbm<S> {
+    source: S,
+    octaves: u32,
+    lacunarity: f64,
+    gain: f64,
+}
+
+/// Ridged multifractal: layers inverted absolute octaves, producing sharp crests like
+/// mountain ridges. Values are in the range `[-1.0, 1.0]` for sources in that range.
+#[derive(Clone, Debug)]
+pub struct Ridged<S> {
+    source: S,
+    octaves: u32,
+    lacunarity: f64,
+    gain: f64,
+}
+
+/// Turbulence: layers absolute octaves, producing billowy creases. Values are in the
+/// range `[0.0, 1.0]` for sources in `[-1.0, 1.0]`.
+#[derive(Clone, Debug)]
+pub struct Turbulence<S> {
+    source: S,
+    octaves: u32,
+    lacunarity: f64,
+    gain: f64,
+}
+
+impl_fractal!(Fbm, Ridged, Turbulence);
+
+impl<const N: usize, S: NoiseFn<N>> NoiseFn<N> for Fbm<S> {
+    #[inline]
+    fn get(&self, point: [f64; N]) -> f64 {
+        self.accumulate(point, |n| n)
+    }
+}
+
+impl<const N: usize, S: NoiseFn<N>> NoiseFn<N> for Ridged<S> {
+    #[inline]
+    fn get(&self, point: [f64; N]) -> f64 {
+        self.accumulate(point, |n| (1.0 - n.abs()).powi(2))
+            .mul_add(2.0, -1.0)
+    }
+}
+
+impl<const N: usize, S: NoiseFn<N>> NoiseFn<N> for Turbulence<S> {
+    #[inline]
+    fn get(&self, point: [f64; N]) -> f64 {
+        self.accumulate(point, f64::abs)
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{Rng, ShuffleSlice};
+
+mod fractal;
+mod open_simplex;
+mod perlin;
+mod value;
+mod worley;
+
+pub use fractal::*;
+pub use open_simplex::*;
+pub use perlin::*;
+pub use value::*;
+pub use worley::*;
+
+/// A coherent noise function over `N`-dimensional space.
+///
+/// Nearby points give similar values, and the same point always gives the same value.
+pub trait NoiseFn<const N: usize> {
+    fn get(&self, point: [f64; N]) -> f64;
+}
+
+impl<const N: usize, T: NoiseFn<N> + ?Sized> NoiseFn<N> for &T {
+    #[inline]
+    fn get(&self, point: [f64; N]) -> f64 {
+        (**self).get(point)
+    }
+}
+
+/// Number of entries in the lattice hash tables. Noise repeats with this period.
+const TABLE_SIZE: usize = 256;
+
+/// A shuffled permutation used to hash lattice coordinates.
+#[derive(Clone, Debug)]
+struct PermutationTable([u8; TABLE_SIZE]);
+
+impl PermutationTable {
+    #[inline]
+    fn new(rng: &mut Rng) -> Self {
+        let mut perm: [u8; TABLE_SIZE] = core::array::from_fn(|i| i as u8);
+        perm.shuffle(rng);
+
+        Self(perm)
+    }
+
+    /// Hashes a lattice point to an index in `0..TABLE_SIZE`.
+    #[inline]
+    fn hash<const N: usize>(&self, cell: [i64; N]) -> usize {
+        cell.iter().fold(0, |h, &c| {
+            self.0[(h + (c as usize & (TABLE_SIZE - 1))) & (TABLE_SIZE - 1)] as usize
+        })
+    }
+}
+
+/// Splits a point into its lattice cell and the offset inside that cell.
+#[inline]
+fn split_cell<const N: usize>(point: [f64; N]) -> ([i64; N], [f64; N]) {
+    let cell = point.map(|x| x.floor() as i64);
+    let frac = core::array::from_fn(|i| point[i] - cell[i] as f64);
+
+    (cell, frac)
+}
+
+/// Quintic smoothstep, `6t^5 - 15t^4 + 10t^3`.
+#[inline]
+fn fade(t: f64) -> f64 {
+    t * t * t * t.mul_add(t.mul_add(6.0, -15.0), 10.0)
+}
+
+#[inline]
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    t.mul_add(b - a, a)
+}
+
+/// The most dimensions the lattice noises support, bounding the corner buffer below.
+const MAX_DIMENSIONS: usize = 8;
+
+/// Interpolates between the `2^N` corner values of a lattice cell.
+///
+/// Corner `c` is the one whose `i`-th offset is bit `i` of `c`.
+#[inline]
+fn interpolate_corners<const N: usize>(frac: [f64; N], corner: impl Fn(usize) -> f64) -> f64 {
+    const {
+        assert!(
+            N <= MAX_DIMENSIONS,
+            "lattice noise supports up to 8 dimensions"
+        )
+    };
+
+    let mut corners = [0.0; 1 << MAX_DIMENSIONS];
+
+    for (c, value) in corners[..1 << N].iter_mut().enumerate() {
+        *value = corner(c);
+    }
+
+    // Collapse one axis at a time, starting from the highest bit
+    for axis in (0..N).rev() {
+        let half = 1 << axis;
+        let t = fade(frac[axis]);
+
+        for c in 0..half {
+            corners[c] = lerp(corners[c], corners[c + half], t);
+        }
+    }
+
+    corners[0]
+}
+
+#[test]
+fn test_noise_range() {
+    // Returns the largest magnitude seen
+    fn check<const N: usize>(noise: &impl NoiseFn<N>, min: f64, max: f64) -> f64 {
+        let rng = &mut Rng::from_seed(1);
+        let mut largest: f64 = 0.0;
+
+        for _ in 0..10_000 {
+            let point = core::array::from_fn(|_| rng.gen_range(-100.0..100.0));
+            let v = noise.get(point);
+
+            assert!((min..=max).contains(&v), "{v} out of range at {point:?}");
+            assert_eq!(v, noise.get(point));
+
+            largest = largest.max(v.abs());
+        }
+
+        largest
+    }
+
+    check::<1>(&Perlin::new(3), -1.0, 1.0);
+    check::<2>(&Perlin::new(3), -1.0, 1.0);
+    check::<3>(&Perlin::new(3), -1.0, 1.0);
+    check::<4>(&Perlin::new(3), -1.0, 1.0);
+
+    // OpenSimplex2 is normalized per dimension, so it spans most of the range in each
+    let spans = [
+        check::<1>(&OpenSimplex2::new(3), -1.0, 1.0),
+        check::<2>(&OpenSimplex2::new(3), -1.0, 1.0),
+        check::<3>(&OpenSimplex2::new(3), -1.0, 1.0),
+        check::<4>(&OpenSimplex2::new(3), -1.0, 1.0),
+    ];
+    assert!(spans.iter().all(|&s| s > 0.7), "{spans:?}");
+
+    check::<1>(&Value::new(3), -1.0, 1.0);
+    check::<2>(&Value::new(3), -1.0, 1.0);
+    check::<3>(&Value::new(3), -1.0, 1.0);
+    check::<4>(&Value::new(3), -1.0, 1.0);
+
+    // The feature point of the cell holding the point is at most `sqrt(N)` away
+    check::<1>(&Worley::new(3), 0.0, 1.0);
+    check::<2>(&Worley::new(3), 0.0, 2f64.sqrt());
+    check::<3>(&Worley::new(3), 0.0, 3f64.sqrt());
+    check::<4>(&Worley::new(3), 0.0, 2.0);
+    check::<2>(&Fbm::new(Perlin::new(3)), -1.0, 1.0);
+    check::<3>(&Ridged::new(OpenSimplex2::new(3)), -1.0, 1.0);
+    check::<2>(&Turbulence::new(Value::new(3)), 0.0, 1.0);
+}
+
+#[test]
+fn test_noise_continuous() {
+    // A vertex in range that's skipped would make the value jump as the point moves. The
+    // second difference over small steps stays tiny for smooth noise but not across a jump
+    fn check<const N: usize>(noise: &impl NoiseFn<N>) {
+        let rng = &mut Rng::from_seed(2);
+
+        for _ in 0..10_000 {
+            let point: [f64; N] = core::array::from_fn(|_| rng.gen_range(-100.0..100.0));
+            let step: [f64; N] = rng.gen_unit_vector().map(|x: f64| x * 1e-3);
+
+            let before = noise.get(core::array::from_fn(|i| point[i] - step[i]));
+            let after = noise.get(core::array::from_fn(|i| point[i] + step[i]));
+
+            assert!(
+                (before - 2.0 * noise.get(point) + after).abs() < 1e-3,
+                "jump at {point:?}"
+            );
+        }
+    }
+
+    check::<1>(&OpenSimplex2::new(3));
+    check::<2>(&OpenSimplex2::new(3));
+    check::<3>(&OpenSimplex2::new(3));
+    check::<4>(&OpenSimplex2::new(3));
+}
+
+#[test]
+fn test_noise_seed() {
+    let a = Perlin::<2>::new(10);
+    let b = Perlin::<2>::new(10);
+    let c = Perlin::<2>::new(11);
+
+    assert_eq!(a.get([0.3, 0.7]), b.get([0.3, 0.7]));
+    assert_ne!(a.get([0.3, 0.7]), c.get([0.3, 0.7]));
+}
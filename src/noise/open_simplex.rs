@@ -0,0 +1,192 @@
+use crate::Rng;
+use crate::noise::{NoiseFn, PermutationTable, TABLE_SIZE};
+
+/// Squared radius of each lattice vertex's area of influence. This is the largest radius
+/// at which the vertices looked at below are the only ones in range, so the noise stays
+/// continuous. The reference implementation uses `0.6` in 3D and 4D, which lets vertices it
+/// doesn't look at come into range.
+const RADIUS_SQ: f64 = 0.5;
+
+/// `(sqrt(3) - 1) / 2`, maps the plane onto the triangular lattice's skewed grid.
+const SKEW_2D: f64 = 0.366_025_403_784_438_6;
+/// `(3 - sqrt(3)) / 6`, maps skewed coordinates back.
+const UNSKEW_2D: f64 = 0.211_324_865_405_187_1;
+
+/// `(1 / sqrt(5) - 1) / 4`, maps 4D space onto the skewed grid of one lattice copy.
+const SKEW_4D: f64 = -0.138_196_601_125_010_5;
+/// `(sqrt(5) - 1) / 4`, maps skewed coordinates back.
+const UNSKEW_4D: f64 = 0.309_016_994_374_947_4;
+
+// The summed contributions are largest where every gradient points along its offset, so
+// dividing by that maximum keeps values in `[-1.0, 1.0]`. A lone vertex at distance `d`
+// contributes `(r² - d²)^4 * d`, which peaks at `d = r / 3`. That is the maximum in 1D, where
+// only one vertex can be that close. In higher dimensions several vertices overlap, and the
+// maximum was found by numerically maximizing the sum over a lattice cell. Each constant is
+// the inverse, rounded down.
+const NORMALIZER_1D: f64 = 108.7342;
+const NORMALIZER_2D: f64 = 99.2043;
+const NORMALIZER_3D: f64 = 107.6534;
+const NORMALIZER_4D: f64 = 108.5678;
+
+/// OpenSimplex2 gradient noise in 1 to 4 dimensions.
+///
+/// Sums radially-attenuated gradient contributions from the lattice vertices near the
+/// point. It uses OpenSimplex2's lattices: a triangular one in 2D, a body-centered cubic one
+/// in 3D and five offset copies of the simplex lattice in 4D, which avoid the axis-aligned
+/// artifacts of Perlin noise. In 1D the vertices are the integers. Values are in the range
+/// `[-1.0, 1.0]`.
+#[derive(Clone, Debug)]
+pub struct OpenSimplex2<const N: usize> {
+    perm: PermutationTable,
+    gradients: Vec<[f64; N]>,
+}
+
+impl<const N: usize> OpenSimplex2<N> {
+    /// Creates new OpenSimplex2 noise whose tables are generated from `Rng::from_seed(seed)`.
+    #[inline]
+    pub fn new(seed: u64) -> Self {
+        let rng = &mut Rng::from_seed(seed);
+
+        Self {
+            perm: PermutationTable::new(rng),
+            gradients: (0..TABLE_SIZE).map(|_| rng.gen_unit_vector()).collect(),
+        }
+    }
+
+    /// The contribution of the vertex hashed from `key`, with `offset` from the vertex to
+    /// the point.
+    #[inline]
+    fn contribution(&self, key: [i64; N], offset: [f64; N]) -> f64 {
+        let falloff = RADIUS_SQ - offset.iter().map(|x| x * x).sum::<f64>();
+
+        if falloff <= 0.0 {
+            return 0.0;
+        }
+
+        let gradient = &self.gradients[self.perm.hash(key)];
+        let dot = (0..N).map(|i| gradient[i] * offset[i]).sum::<f64>();
+
+        falloff.powi(4) * dot
+    }
+}
+
+impl NoiseFn<1> for OpenSimplex2<1> {
+    #[inline]
+    fn get(&self, [x]: [f64; 1]) -> f64 {
+        let cell = x.floor();
+        let frac = x - cell;
+        let cell = cell as i64;
+
+        let total = self.contribution([cell], [frac]) + self.contribution([cell + 1], [frac - 1.0]);
+
+        total * NORMALIZER_1D
+    }
+}
+
+impl NoiseFn<2> for OpenSimplex2<2> {
+    #[inline]
+    fn get(&self, [x, y]: [f64; 2]) -> f64 {
+        // Skew the input to find the triangle containing the point
+        let s = (x + y) * SKEW_2D;
+        let (xs, ys) = ((x + s).floor(), (y + s).floor());
+        let (xi, yi) = (x + s - xs, y + s - ys);
+        let (i, j) = (xs as i64, ys as i64);
+
+        let t = (xi + yi) * UNSKEW_2D;
+        let (dx, dy) = (xi - t, yi - t);
+
+        // The middle corner is a step along whichever axis the point is further along
+        let (mi, mj) = if xi > yi { (1, 0) } else { (0, 1) };
+
+        let total = self.contribution([i, j], [dx, dy])
+            + self.contribution(
+                [i + mi, j + mj],
+                [dx - mi as f64 + UNSKEW_2D, dy - mj as f64 + UNSKEW_2D],
+            )
+            + self.contribution(
+                [i + 1, j + 1],
+                [dx - 1.0 + 2.0 * UNSKEW_2D, dy - 1.0 + 2.0 * UNSKEW_2D],
+            );
+
+        total * NORMALIZER_2D
+    }
+}
+
+impl NoiseFn<3> for OpenSimplex2<3> {
+    #[inline]
+    fn get(&self, [x, y, z]: [f64; 3]) -> f64 {
+        // Turn the input half a turn about the main diagonal, OpenSimplex2's default
+        // orientation, so the cubic lattice isn't aligned with the axes
+        let r = (x + y + z) * (2.0 / 3.0);
+        let point = [r - x, r - y, r - z];
+
+        // The body-centered cubic lattice is two cubic grids, the second shifted by a half
+        // in every axis. Vertices are keyed by their doubled coordinates, which are even on
+        // the first grid and odd on the second
+        let mut total = 0.0;
+
+        for grid in 0..2 {
+            let shifted = point.map(|x| x - grid as f64 * 0.5);
+            let nearest = shifted.map(f64::round);
+            let offset: [f64; 3] = core::array::from_fn(|i| shifted[i] - nearest[i]);
+            let key = nearest.map(|x| x as i64 * 2 + grid);
+
+            total += self.contribution(key, offset);
+
+            // The second-closest vertex is a step along the axis the point is furthest along
+            let axis = (0..3)
+                .max_by(|&a, &b| offset[a].abs().total_cmp(&offset[b].abs()))
+                .unwrap();
+            let step = if offset[axis] < 0.0 { -1 } else { 1 };
+
+            let mut key = key;
+            let mut offset = offset;
+            key[axis] += step * 2;
+            offset[axis] -= step as f64;
+
+            total += self.contribution(key, offset);
+        }
+
+        total * NORMALIZER_3D
+    }
+}
+
+impl NoiseFn<4> for OpenSimplex2<4> {
+    #[inline]
+    fn get(&self, point: [f64; 4]) -> f64 {
+        let s = point.iter().sum::<f64>() * SKEW_4D;
+        let skewed = point.map(|x| x + s);
+
+        // The lattice is five copies of the simplex lattice, each shifted a fifth of the way
+        // along the main diagonal. Vertices are keyed by their coordinates times five plus
+        // the copy, and only one vertex of each copy is ever in range
+        let mut total = 0.0;
+
+        for copy in 0..5 {
+            let shifted = skewed.map(|x| x - copy as f64 * 0.2);
+            let cell = shifted.map(|x| x.floor() as i64);
+            let frac: [f64; 4] = core::array::from_fn(|i| shifted[i] - cell[i] as f64);
+
+            // Walk the simplex's corners along the axes in order of decreasing offset
+            let mut order = [0, 1, 2, 3];
+            order.sort_by(|&a, &b| frac[b].total_cmp(&frac[a]));
+
+            let mut vertex = [0i64; 4];
+
+            for k in 0..=4 {
+                if k > 0 {
+                    vertex[order[k - 1]] += 1;
+                }
+
+                let skewed_offset: [f64; 4] = core::array::from_fn(|i| frac[i] - vertex[i] as f64);
+                let t = skewed_offset.iter().sum::<f64>() * UNSKEW_4D;
+                let offset = skewed_offset.map(|x| x + t);
+                let key = core::array::from_fn(|i| (cell[i] + vertex[i]) * 5 + copy);
+
+                total += self.contribution(key, offset);
+            }
+        }
+
+        total * NORMALIZER_4D
+    }
+}
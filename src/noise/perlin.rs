@@ -0,0 +1,44 @@
+use crate::Rng;
+use crate::noise::{NoiseFn, PermutationTable, TABLE_SIZE, interpolate_corners, split_cell};
+
+/// Classic gradient (Perlin) noise in `N` dimensions.
+///
+/// Values are in the range `[-1.0, 1.0]` and are `0.0` on every lattice point. Supports up
+/// to 8 dimensions.
+#[derive(Clone, Debug)]
+pub struct Perlin<const N: usize> {
+    perm: PermutationTable,
+    gradients: Vec<[f64; N]>,
+}
+
+impl<const N: usize> Perlin<N> {
+    /// Creates new Perlin noise whose tables are generated from `Rng::from_seed(seed)`.
+    #[inline]
+    pub fn new(seed: u64) -> Self {
+        let rng = &mut Rng::from_seed(seed);
+
+        Self {
+            perm: PermutationTable::new(rng),
+            gradients: (0..TABLE_SIZE).map(|_| rng.gen_unit_vector()).collect(),
+        }
+    }
+}
+
+impl<const N: usize> NoiseFn<N> for Perlin<N> {
+    #[inline]
+    fn get(&self, point: [f64; N]) -> f64 {
+        let (cell, frac) = split_cell(point);
+
+        let value = interpolate_corners(frac, |c| {
+            let corner: [i64; N] = core::array::from_fn(|i| cell[i] + (c >> i & 1) as i64);
+            let gradient = &self.gradients[self.perm.hash(corner)];
+
+            (0..N)
+                .map(|i| gradient[i] * (frac[i] - (c >> i & 1) as f64))
+                .sum()
+        });
+
+        // Unit gradients give a theoretical maximum of sqrt(N) / 2
+        (value * 2.0 / (N as f64).sqrt()).clamp(-1.0, 1.0)
+    }
+}
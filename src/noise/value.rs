@@ -0,0 +1,37 @@
+use crate::Rng;
+use crate::noise::{NoiseFn, PermutationTable, TABLE_SIZE, interpolate_corners, split_cell};
+
+/// Value noise, smoothly interpolating random values placed on the lattice points.
+///
+/// Works in up to 8 dimensions. Values are in the range `[-1.0, 1.0]`.
+#[derive(Clone, Debug)]
+pub struct Value {
+    perm: PermutationTable,
+    values: [f64; TABLE_SIZE],
+}
+
+impl Value {
+    /// Creates new value noise whose tables are generated from `Rng::from_seed(seed)`.
+    #[inline]
+    pub fn new(seed: u64) -> Self {
+        let rng = &mut Rng::from_seed(seed);
+
+        Self {
+            perm: PermutationTable::new(rng),
+            values: core::array::from_fn(|_| rng.gen_range(-1.0..=1.0)),
+        }
+    }
+}
+
+impl<const N: usize> NoiseFn<N> for Value {
+    #[inline]
+    fn get(&self, point: [f64; N]) -> f64 {
+        let (cell, frac) = split_cell(point);
+
+        interpolate_corners(frac, |c| {
+            let corner: [i64; N] = core::array::from_fn(|i| cell[i] + (c >> i & 1) as i64);
+
+            self.values[self.perm.hash(corner)]
+        })
+    }
+}
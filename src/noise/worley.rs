@@ -0,0 +1,92 @@
+use crate::Rng;
+use crate::noise::{NoiseFn, PermutationTable, TABLE_SIZE, split_cell};
+
+/// Which distance a [`Worley`] noise returns.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum WorleyReturn {
+    /// Distance to the nearest feature point.
+    #[default]
+    F1,
+    /// Distance to the second-nearest feature point.
+    F2,
+    /// `F2 - F1`, which highlights the borders between cells.
+    F2MinusF1,
+}
+
+/// Worley (cellular) noise in any number of dimensions.
+///
+/// Every lattice cell holds one randomly placed feature point, and the value is based on
+/// the distance to the closest ones, in lattice units. Values are non-negative.
+#[derive(Clone, Debug)]
+pub struct Worley {
+    perm: PermutationTable,
+    jitter: [f64; TABLE_SIZE],
+    ret: WorleyReturn,
+}
+
+impl Worley {
+    /// Creates new Worley noise whose tables are generated from `Rng::from_seed(seed)`.
+    #[inline]
+    pub fn new(seed: u64) -> Self {
+        let rng = &mut Rng::from_seed(seed);
+
+        Self {
+            perm: PermutationTable::new(rng),
+            jitter: core::array::from_fn(|_| rng.gen_value()),
+            ret: WorleyReturn::F1,
+        }
+    }
+
+    /// Sets which distance is returned. Defaults to [`WorleyReturn::F1`].
+    #[inline]
+    #[must_use]
+    pub fn with_return(mut self, ret: WorleyReturn) -> Self {
+        self.ret = ret;
+        self
+    }
+}
+
+impl<const N: usize> NoiseFn<N> for Worley {
+    #[inline]
+    fn get(&self, point: [f64; N]) -> f64 {
+        let (cell, frac) = split_cell(point);
+
+        let mut f1 = f64::INFINITY;
+        let mut f2 = f64::INFINITY;
+
+        // Check the 3^N cells surrounding the point
+        for n in 0..3usize.pow(N as u32) {
+            let mut rest = n;
+            let offset: [i64; N] = core::array::from_fn(|_| {
+                let o = (rest % 3) as i64 - 1;
+                rest /= 3;
+                o
+            });
+
+            let neighbor: [i64; N] = core::array::from_fn(|i| cell[i] + offset[i]);
+            let hash = self.perm.hash(neighbor);
+
+            let dist_sq = (0..N)
+                .map(|i| {
+                    let feature = offset[i] as f64
+                        + self.jitter[self.perm.0[(hash + i) % TABLE_SIZE] as usize];
+
+                    (feature - frac[i]).powi(2)
+                })
+                .sum::<f64>();
+
+            if dist_sq < f1 {
+                f2 = f1;
+                f1 = dist_sq;
+            } else if dist_sq < f2 {
+                f2 = dist_sq;
+            }
+        }
+
+        match self.ret {
+            WorleyReturn::F1 => f1.sqrt(),
+            WorleyReturn::F2 => f2.sqrt(),
+            WorleyReturn::F2MinusF1 => f2.sqrt() - f1.sqrt(),
+        }
+    }
+}
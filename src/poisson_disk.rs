@@ -0,0 +1,228 @@
+use crate::Rng;
+
+/// Poisson-disk sampling in two dimensions.
+pub type PoissonDisk2 = PoissonDisk<2>;
+
+/// Poisson-disk sampling in three dimensions.
+pub type PoissonDisk3 = PoissonDisk<3>;
+
+/// Generates blue-noise point sets inside an axis-aligned bounding box using
+/// Bridson's fast Poisson-disk sampling.
+///
+/// No two points are closer than the sampling radius, while the box is still filled
+/// densely. The same seed always produces the same layout.
+#[derive(Clone, Debug)]
+pub struct PoissonDisk<const N: usize> {
+    min: [f64; N],
+    max: [f64; N],
+    radius: f64,
+    attempts: usize,
+}
+
+impl<const N: usize> PoissonDisk<N> {
+    /// Creates a new PoissonDisk sampler over the box `min..max` with the given minimum
+    /// distance between points.
+    ///
+    /// # Panics
+    /// - If `radius` is not positive
+    /// - If `min` is not less than `max` on every axis
+    #[inline]
+    pub fn new(min: [f64; N], max: [f64; N], radius: f64) -> Self {
+        assert!(radius > 0.0, "Poisson-disk radius must be positive");
+        assert!(
+            min.iter().zip(&max).all(|(lo, hi)| lo < hi),
+            "Invalid bounding box: {min:?}..{max:?}"
+        );
+
+        Self {
+            min,
+            max,
+            radius,
+            attempts: 30,
+        }
+    }
+
+    /// Sets how many candidates are tried around each point before it is retired.
+    ///
+    /// Higher values pack points more tightly at the cost of speed. Defaults to `30`.
+    #[inline]
+    #[must_use]
+    pub fn with_attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Generates points that are all at least the sampler's radius apart.
+    #[inline]
+    pub fn sample(&self, rng: &mut Rng) -> Vec<[f64; N]> {
+        let radius = self.radius;
+
+        self.sample_with(rng, |_| radius)
+    }
+
+    /// Generates points with a spacing that varies over the box.
+    ///
+    /// `radius_at` returns the minimum distance around a position; it is clamped to the
+    /// sampler's radius, which acts as the upper bound. Two points `a` and `b` are always
+    /// at least `max(radius_at(a), radius_at(b))` apart.
+    pub fn sample_with(
+        &self,
+        rng: &mut Rng,
+        mut radius_at: impl FnMut([f64; N]) -> f64,
+    ) -> Vec<[f64; N]> {
+        if N == 0 {
+            return Vec::new();
+        }
+
+        let mut radius_at = |p: [f64; N]| radius_at(p).clamp(f64::EPSILON, self.radius);
+
+        // Cells are sized so that a cell's diagonal equals the radius
+        let cell = self.radius / (N as f64).sqrt();
+        let dims: [usize; N] =
+            core::array::from_fn(|i| (((self.max[i] - self.min[i]) / cell).ceil() as usize).max(1));
+        let reach = (self.radius / cell).ceil() as isize;
+
+        let mut grid: Vec<Vec<usize>> = vec![Vec::new(); dims.iter().product()];
+        let mut points: Vec<[f64; N]> = Vec::new();
+        let mut radii: Vec<f64> = Vec::new();
+        let mut active: Vec<usize> = Vec::new();
+
+        let cell_of = |p: &[f64; N]| -> [isize; N] {
+            core::array::from_fn(|i| {
+                (((p[i] - self.min[i]) / cell) as isize).clamp(0, dims[i] as isize - 1)
+            })
+        };
+        let grid_index = |c: &[isize; N]| -> usize {
+            c.iter()
+                .zip(&dims)
+                .fold(0, |idx, (&c, &dim)| idx * dim + c as usize)
+        };
+
+        let first: [f64; N] = core::array::from_fn(|i| rng.gen_range(self.min[i]..self.max[i]));
+
+        radii.push(radius_at(first));
+        grid[grid_index(&cell_of(&first))].push(0);
+        points.push(first);
+        active.push(0);
+
+        while !active.is_empty() {
+            let slot = rng.gen_range(0..active.len());
+            let center = points[active[slot]];
+            let center_radius = radii[active[slot]];
+
+            let mut found = false;
+
+            for _ in 0..self.attempts {
+                // Candidates are spawned in the shell between r and 2r around the point
+                let dir = rng.gen_unit_vector::<f64, N>();
+                let dist = rng.next_f64().mul_add(center_radius, center_radius);
+                let candidate: [f64; N] = core::array::from_fn(|i| dir[i].mul_add(dist, center[i]));
+
+                if (0..N).any(|i| candidate[i] < self.min[i] || candidate[i] >= self.max[i]) {
+                    continue;
+                }
+
+                let candidate_radius = radius_at(candidate);
+                let base = cell_of(&candidate);
+
+                let mut offset = [-reach; N];
+                let mut clear = true;
+
+                // Walk every cell within reach of the candidate's cell
+                'cells: loop {
+                    let neighbor: [isize; N] = core::array::from_fn(|i| base[i] + offset[i]);
+
+                    if (0..N).all(|i| (0..dims[i] as isize).contains(&neighbor[i])) {
+                        for &other in &grid[grid_index(&neighbor)] {
+                            let min_dist = candidate_radius.max(radii[other]);
+                            let dist_sq = (0..N)
+                                .map(|i| (candidate[i] - points[other][i]).powi(2))
+                                .sum::<f64>();
+
+                            if dist_sq < min_dist * min_dist {
+                                clear = false;
+                                break 'cells;
+                            }
+                        }
+                    }
+
+                    let mut axis = 0;
+
+                    loop {
+                        if axis == N {
+                            break 'cells;
+                        }
+
+                        offset[axis] += 1;
+
+                        if offset[axis] <= reach {
+                            break;
+                        }
+
+                        offset[axis] = -reach;
+                        axis += 1;
+                    }
+                }
+
+                if clear {
+                    let idx = points.len();
+
+                    grid[grid_index(&base)].push(idx);
+                    points.push(candidate);
+                    radii.push(candidate_radius);
+                    active.push(idx);
+
+                    found = true;
+                    break;
+                }
+            }
+
+            if !found {
+                active.swap_remove(slot);
+            }
+        }
+
+        points
+    }
+}
+
+#[test]
+fn test_poisson_disk() {
+    fn min_dist<const N: usize>(points: &[[f64; N]]) -> f64 {
+        let mut min = f64::INFINITY;
+
+        for (i, a) in points.iter().enumerate() {
+            for b in &points[i + 1..] {
+                let d = (0..N).map(|k| (a[k] - b[k]).powi(2)).sum::<f64>().sqrt();
+                min = min.min(d);
+            }
+        }
+
+        min
+    }
+
+    let points = PoissonDisk2::new([0.0, 0.0], [10.0, 10.0], 0.5).sample(&mut Rng::from_seed(7));
+
+    assert!(points.len() > 200);
+    assert!(min_dist(&points) >= 0.5);
+    assert!(
+        points
+            .iter()
+            .all(|p| p.iter().all(|x| (0.0..10.0).contains(x)))
+    );
+
+    // Same seed, same layout
+    let again = PoissonDisk2::new([0.0, 0.0], [10.0, 10.0], 0.5).sample(&mut Rng::from_seed(7));
+    assert_eq!(points, again);
+
+    let points = PoissonDisk3::new([0.0; 3], [4.0; 3], 0.5).sample(&mut Rng::from_seed(7));
+    assert!(points.len() > 200);
+    assert!(min_dist(&points) >= 0.5);
+
+    // Denser on the left, sparser on the right
+    let points = PoissonDisk2::new([0.0, 0.0], [10.0, 10.0], 1.0)
+        .sample_with(&mut Rng::from_seed(7), |[x, _]| 0.25 + x * 0.075);
+    let left = points.iter().filter(|p| p[0] < 5.0).count();
+
+    assert!(left > 2 * (points.len() - left));
+}
@@ -4,11 +4,53 @@ use core::cell::RefCell;
 ///
 /// This RNG is suitable for simulations, games, and randomized algorithms
 /// where cryptographic security is not required.
+///
+/// # Value stability
+/// A generator created with [`Rng::from_seed`] produces the same values on every platform
+/// and in every release with the same major version (minor version before `1.0`). This
+/// covers the raw WyRand sequence and everything derived from it: `gen_value`, `gen_range`,
+/// `gen_bool`, `fill` and friends, `shuffle`, `sample`, `sample_multi`, [`CdfSampler`],
+/// strings, [`hash_rng`] and [`RandomAccessRng`]. Changing any of these outputs is a breaking
+/// change, and known-answer tests fail when it happens.
+///
+/// Not covered are [`Rng::new`], which is seeded from the operating system, and the
+/// `RandomInstance` fuzzing generators, which may change to find bugs more effectively.
+///
+/// [`CdfSampler`]: crate::CdfSampler
+/// [`hash_rng`]: crate::hash_rng
+/// [`RandomAccessRng`]: crate::RandomAccessRng
 #[derive(Copy, Clone, Debug)]
 pub struct Rng {
     seed: u64,
 }
 
+/// The WyHash secrets; the first two are also WyRand's increment and output xor.
+pub(crate) const WYP0: u64 = 0xa0761d6478bd642f;
+pub(crate) const WYP1: u64 = 0xe7037ed1a0b428db;
+pub(crate) const WYP2: u64 = 0x8ebc6af09c88c6e3;
+
+/// Multiplies two values to 128 bits and folds the halves together (the WyHash mixer).
+#[inline]
+pub(crate) fn wymix(a: u64, b: u64) -> u64 {
+    let r = a as u128 * b as u128;
+
+    (r as u64) ^ (r >> 64) as u64
+}
+
+/// The WyRand output function, applied to the state after each increment.
+#[inline]
+fn wyrand_mix(t: u64) -> u64 {
+    wymix(t, t ^ WYP1)
+}
+
+/// Maps a random `u64` to a uniformly distributed `f64` in the range `[0.0, 1.0)`.
+#[inline]
+pub(crate) fn u64_to_f64(x: u64) -> f64 {
+    const F64_DENOM: f64 = 1.0 / (1u64 << 53) as f64;
+
+    (x >> 11) as f64 * F64_DENOM
+}
+
 thread_local! {
     static THREAD_RNG: RefCell<Rng> = RefCell::new({
         let mut buf = [0u8; 8];
@@ -38,10 +80,20 @@ impl Rng {
     /// This is the core method that drives all other random number generation.
     #[inline]
     pub(crate) fn next_u64(&mut self) -> u64 {
-        self.seed = self.seed.wrapping_add(0xa0761d6478bd642f);
-        let t = self.seed;
-        let s = t ^ t >> 32;
-        s.wrapping_mul(t) ^ s >> 32
+        self.seed = self.seed.wrapping_add(WYP0);
+        wyrand_mix(self.seed)
+    }
+
+    /// Generates the next `N` random `u64`s at once.
+    ///
+    /// Returns exactly what `N` calls to `next_u64` would, but since WyRand's state is a
+    /// simple counter the lanes are independent and can be computed in parallel.
+    #[inline]
+    pub(crate) fn next_u64_lanes<const N: usize>(&mut self) -> [u64; N] {
+        let base = self.seed;
+        self.seed = base.wrapping_add(WYP0.wrapping_mul(N as u64));
+
+        core::array::from_fn(|i| wyrand_mix(base.wrapping_add(WYP0.wrapping_mul(i as u64 + 1))))
     }
 
     /// Generates a random `f64` in the range `[0.0, 1.0)`.
@@ -49,9 +101,7 @@ impl Rng {
     /// The value is uniformly distributed across the possible range.
     #[inline]
     pub(crate) fn next_f64(&mut self) -> f64 {
-        const F64_DENOM: f64 = 1.0 / (1u64 << 53) as f64;
-
-        (self.next_u64() >> 11) as f64 * F64_DENOM
+        u64_to_f64(self.next_u64())
     }
 
     /// Returns `true` with a given probability.
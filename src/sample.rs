@@ -1,4 +1,4 @@
-use crate::Rng;
+use crate::{GeomFloat, PolygonSampler, Rng, SampleIter};
 
 pub trait SampleSlice {
     /// The element type.
@@ -60,6 +60,21 @@ impl<T> SampleSlice for [T] {
     }
 }
 
+/// A source of random samples of type `T`.
+pub trait Distribution<T> {
+    /// Draws one sample.
+    fn sample(&self, rng: &mut Rng) -> T;
+
+    /// Returns an infinite iterator of samples, borrowing the generator.
+    #[inline]
+    fn sample_iter(self, rng: &mut Rng) -> SampleIter<'_, Self, T>
+    where
+        Self: Sized,
+    {
+        SampleIter::new(self, rng)
+    }
+}
+
 /// Cumulative distribution function sampling
 #[derive(Clone, Debug)]
 pub struct CdfSampler<T> {
@@ -116,6 +131,20 @@ impl<T> CdfSampler<T> {
     }
 }
 
+impl<'a, T> Distribution<&'a T> for &'a CdfSampler<T> {
+    #[inline]
+    fn sample(&self, rng: &mut Rng) -> &'a T {
+        CdfSampler::sample(self, rng)
+    }
+}
+
+impl<T: GeomFloat> Distribution<[T; 2]> for &PolygonSampler<T> {
+    #[inline]
+    fn sample(&self, rng: &mut Rng) -> [T; 2] {
+        PolygonSampler::sample(self, rng)
+    }
+}
+
 #[inline]
 pub fn weighted_sample<'a, T>(weights: &'a Vec<(T, f32)>, rng: &'a mut Rng) -> Option<&'a T> {
     let weight_sum = weights.iter().map(|(_, w)| w).sum::<f32>();
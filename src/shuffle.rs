@@ -26,4 +26,24 @@ fn test_shuffle() {
     vec.shuffle(Rng::new());
 
     dbg!(vec);
+
+    // All 24 orders of four elements are equally likely
+    let rng = &mut Rng::from_seed(0);
+    let mut counts = [0; 24];
+
+    for _ in 0..24_000 {
+        let mut order = [0, 1, 2, 3];
+        order.shuffle(rng);
+
+        // Rank the permutation to index the counts
+        let rank = (0..4).fold(0, |rank, i| {
+            let smaller = order[i + 1..].iter().filter(|&&x| x < order[i]).count();
+
+            rank * (4 - i) + smaller
+        });
+
+        counts[rank] += 1;
+    }
+
+    assert!(crate::chi_squared_uniform(&counts).passed(0.001));
 }
@@ -0,0 +1,502 @@
+use crate::rng::u64_to_f64;
+
+/// The outcome of one statistical test.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StatTestResult {
+    /// Which test produced the result.
+    pub name: &'static str,
+    /// The test statistic, like the chi-squared sum or the Kolmogorov–Smirnov distance.
+    pub statistic: f64,
+    /// The probability of a statistic at least this extreme from truly random samples.
+    pub p_value: f64,
+}
+
+impl StatTestResult {
+    /// Whether the samples look random at significance level `alpha`, e.g. `0.001`.
+    #[inline]
+    pub fn passed(&self, alpha: f64) -> bool {
+        self.p_value >= alpha
+    }
+}
+
+/// Pearson's chi-squared test of `observed` counts against the expected `probabilities`
+/// of each bin.
+///
+/// Every bin should expect at least 5 samples for the p-value to be accurate.
+///
+/// # Panics
+/// If `observed` and `probabilities` differ in length or have fewer than two bins.
+pub fn chi_squared(observed: &[u64], probabilities: &[f64]) -> StatTestResult {
+    assert_eq!(
+        observed.len(),
+        probabilities.len(),
+        "every bin needs a probability"
+    );
+    assert!(observed.len() >= 2, "chi-squared needs at least two bins");
+
+    let total = observed.iter().sum::<u64>() as f64;
+    let norm = probabilities.iter().sum::<f64>();
+
+    let mut statistic = 0.0;
+    let mut bins = 0;
+
+    for (&count, &p) in observed.iter().zip(probabilities) {
+        let expected = total * p / norm;
+
+        if expected > 0.0 {
+            statistic += (count as f64 - expected).powi(2) / expected;
+            bins += 1;
+        } else if count > 0 {
+            // A sample landed in a bin that should be impossible
+            statistic = f64::INFINITY;
+        }
+    }
+
+    StatTestResult {
+        name: "chi-squared",
+        statistic,
+        p_value: chi_squared_p(statistic, bins.max(2) - 1),
+    }
+}
+
+/// Chi-squared test that every bin of `observed` is equally likely.
+///
+/// Use it on the counts of `gen_range` results, shuffled positions or sampled elements.
+#[inline]
+pub fn chi_squared_uniform(observed: &[u64]) -> StatTestResult {
+    chi_squared(observed, &vec![1.0; observed.len()])
+}
+
+/// Kolmogorov–Smirnov test that `samples` are uniformly distributed in `[0, 1)`.
+///
+/// # Panics
+/// If `samples` is empty.
+#[inline]
+pub fn kolmogorov_smirnov(samples: &[f64]) -> StatTestResult {
+    kolmogorov_smirnov_with(samples, |x| x.clamp(0.0, 1.0))
+}
+
+/// Kolmogorov–Smirnov test that `samples` follow the distribution with the given `cdf`.
+///
+/// # Panics
+/// If `samples` is empty.
+pub fn kolmogorov_smirnov_with(samples: &[f64], cdf: impl Fn(f64) -> f64) -> StatTestResult {
+    assert!(!samples.is_empty(), "Kolmogorov–Smirnov needs samples");
+
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let n = sorted.len() as f64;
+
+    // The largest gap between the empirical and the expected CDF
+    let distance = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| {
+            let expected = cdf(x);
+
+            (expected - i as f64 / n).max((i + 1) as f64 / n - expected)
+        })
+        .fold(0.0, f64::max);
+
+    // Stephens' approximation of the distribution of the distance
+    let sqrt_n = n.sqrt();
+    let lambda = (sqrt_n + 0.12 + 0.11 / sqrt_n) * distance;
+
+    StatTestResult {
+        name: "Kolmogorov–Smirnov",
+        statistic: distance,
+        p_value: kolmogorov_p(lambda),
+    }
+}
+
+/// Tests that consecutive `samples` are uncorrelated, using the lag-1 autocorrelation.
+///
+/// # Panics
+/// If there are fewer than three samples.
+pub fn serial_correlation(samples: &[f64]) -> StatTestResult {
+    assert!(samples.len() >= 3, "serial correlation needs three samples");
+
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>();
+    let covariance = samples
+        .windows(2)
+        .map(|w| (w[0] - mean) * (w[1] - mean))
+        .sum::<f64>();
+
+    let correlation = if variance > 0.0 {
+        covariance / variance
+    } else {
+        1.0
+    };
+
+    // The correlation of independent samples is about normal with variance `1 / n`
+    StatTestResult {
+        name: "serial correlation",
+        statistic: correlation,
+        p_value: normal_two_sided_p(correlation * n.sqrt()),
+    }
+}
+
+/// Marsaglia's birthday spacings test.
+///
+/// `values` are split into years of `birthdays` values each, and the top `day_bits` bits of
+/// each value pick its day. The number of repeated spacings between sorted birthdays is
+/// compared to the Poisson distribution it follows for random values. Good parameters are
+/// 512 birthdays over 24 bits, with at least 50 years.
+///
+/// # Panics
+/// - If `day_bits` isn't in `1..=64`
+/// - If `values` holds less than one year
+pub fn birthday_spacings(values: &[u64], birthdays: usize, day_bits: u32) -> StatTestResult {
+    assert!((1..=64).contains(&day_bits), "day_bits must be in 1..=64");
+    assert!(
+        birthdays > 0 && values.len() >= birthdays,
+        "birthday spacings needs at least one year of values"
+    );
+
+    let mut repeats = 0;
+    let mut days = vec![0u64; birthdays];
+    let mut spacings = vec![0u64; birthdays];
+
+    for year in values.chunks_exact(birthdays) {
+        for (day, &value) in days.iter_mut().zip(year) {
+            *day = value >> (64 - day_bits);
+        }
+
+        days.sort_unstable();
+
+        spacings[0] = days[0];
+
+        for i in 1..birthdays {
+            spacings[i] = days[i] - days[i - 1];
+        }
+
+        spacings.sort_unstable();
+
+        repeats += spacings.windows(2).filter(|w| w[0] == w[1]).count() as u64;
+    }
+
+    let years = (values.len() / birthdays) as f64;
+    let lambda = years * (birthdays as f64).powi(3) / (4.0 * 2f64.powi(day_bits as i32));
+
+    StatTestResult {
+        name: "birthday spacings",
+        statistic: repeats as f64,
+        p_value: poisson_two_sided_p(repeats, lambda),
+    }
+}
+
+/// Knuth's gap test on `samples` in `[0, 1)`.
+///
+/// Counts how many samples fall between consecutive hits of `interval` and compares the
+/// gap lengths to the geometric distribution they follow for random samples.
+///
+/// # Panics
+/// If `interval` isn't a non-empty part of `[0, 1]`.
+pub fn gap_test(samples: &[f64], interval: core::ops::Range<f64>) -> StatTestResult {
+    let p = interval.end - interval.start;
+
+    assert!(
+        interval.start >= 0.0 && interval.end <= 1.0 && p > 0.0,
+        "gap interval must be a non-empty part of [0, 1]"
+    );
+
+    let mut gaps = Vec::new();
+    let mut gap = 0;
+
+    for x in samples {
+        if interval.contains(x) {
+            gaps.push(gap);
+            gap = 0;
+        } else {
+            gap += 1;
+        }
+    }
+
+    // Longer gaps share one bin, sized so that it still expects 5 gaps
+    let mut bins = 1;
+
+    while gaps.len() as f64 * (1.0 - p).powi(bins as i32 + 1) >= 5.0 && bins < 64 {
+        bins += 1;
+    }
+
+    let mut observed = vec![0; bins + 1];
+
+    for gap in gaps {
+        observed[gap.min(bins)] += 1;
+    }
+
+    let mut probabilities = (0..bins)
+        .map(|r| p * (1.0 - p).powi(r as i32))
+        .collect::<Vec<_>>();
+
+    probabilities.push((1.0 - p).powi(bins as i32));
+
+    StatTestResult {
+        name: "gap",
+        ..chi_squared(&observed, &probabilities)
+    }
+}
+
+/// Wald–Wolfowitz runs test: whether `samples` switch between above and below their median
+/// as often as random samples would.
+///
+/// # Panics
+/// If there are fewer than two samples on either side of the median.
+pub fn runs_test(samples: &[f64]) -> StatTestResult {
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let median = sorted.get(sorted.len() / 2).copied().unwrap_or(0.0);
+
+    // Samples equal to the median are skipped
+    let sides = samples
+        .iter()
+        .filter(|&&x| x != median)
+        .map(|&x| x > median)
+        .collect::<Vec<_>>();
+
+    let above = sides.iter().filter(|&&b| b).count() as f64;
+    let below = sides.len() as f64 - above;
+
+    assert!(
+        above >= 2.0 && below >= 2.0,
+        "runs test needs samples on both sides of the median"
+    );
+
+    let runs = 1 + sides.windows(2).filter(|w| w[0] != w[1]).count();
+
+    let n = above + below;
+    let mean = 2.0 * above * below / n + 1.0;
+    let variance = (mean - 1.0) * (mean - 2.0) / (n - 1.0);
+
+    StatTestResult {
+        name: "runs",
+        statistic: runs as f64,
+        p_value: normal_two_sided_p((runs as f64 - mean) / variance.sqrt()),
+    }
+}
+
+/// Runs every test on raw 64-bit outputs drawn from `next`, like `|| rng.gen_value()`.
+///
+/// Draws about 365 thousand values. Tests the high and low bytes for uniformity, so weak low
+/// bits are caught too, and the rest on floats made from the high 53 bits.
+pub fn quality_suite(mut next: impl FnMut() -> u64) -> Vec<StatTestResult> {
+    const COUNT: usize = 1 << 16;
+
+    let values = (0..COUNT * 4).map(|_| next()).collect::<Vec<_>>();
+    let floats = values.iter().map(|&v| u64_to_f64(v)).collect::<Vec<_>>();
+
+    let mut high = [0; 256];
+    let mut low = [0; 256];
+
+    for &v in &values {
+        high[(v >> 56) as usize] += 1;
+        low[(v & 0xff) as usize] += 1;
+    }
+
+    let birthdays = (0..512 * 200).map(|_| next()).collect::<Vec<_>>();
+
+    vec![
+        StatTestResult {
+            name: "chi-squared (high byte)",
+            ..chi_squared_uniform(&high)
+        },
+        StatTestResult {
+            name: "chi-squared (low byte)",
+            ..chi_squared_uniform(&low)
+        },
+        kolmogorov_smirnov(&floats[..COUNT]),
+        serial_correlation(&floats),
+        birthday_spacings(&birthdays, 512, 24),
+        gap_test(&floats, 0.25..0.5),
+        runs_test(&floats),
+    ]
+}
+
+/// The probability that a chi-squared statistic with `df` degrees of freedom is at least
+/// `statistic`.
+fn chi_squared_p(statistic: f64, df: usize) -> f64 {
+    gamma_q(df as f64 / 2.0, statistic / 2.0)
+}
+
+/// The probability that a standard normal value is at least `|z|` away from zero.
+fn normal_two_sided_p(z: f64) -> f64 {
+    // `erfc(|z| / sqrt(2))`
+    gamma_q(0.5, z * z / 2.0)
+}
+
+/// The probability of a Poisson count at least as far in its tail as `k`.
+fn poisson_two_sided_p(k: u64, lambda: f64) -> f64 {
+    let at_most = gamma_q(k as f64 + 1.0, lambda);
+    let at_least = if k == 0 {
+        1.0
+    } else {
+        1.0 - gamma_q(k as f64, lambda)
+    };
+
+    (2.0 * at_most.min(at_least)).min(1.0)
+}
+
+/// The Kolmogorov distribution's survival function.
+fn kolmogorov_p(lambda: f64) -> f64 {
+    // The series converges too slowly for small distances, which are all likely anyway
+    if lambda < 0.2 {
+        return 1.0;
+    }
+
+    let mut sum = 0.0;
+    let mut sign = 2.0;
+
+    for j in 1..=100 {
+        let term = sign * (-2.0 * (j * j) as f64 * lambda * lambda).exp();
+        sum += term;
+
+        if term.abs() <= 1e-12 * sum.abs() {
+            return sum.clamp(0.0, 1.0);
+        }
+
+        sign = -sign;
+    }
+
+    1.0
+}
+
+/// `ln(Γ(x))` for `x > 0`, using the Lanczos approximation.
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    let x = x - 1.0;
+    let t = x + 7.5;
+
+    let sum = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |sum, (i, c)| {
+            sum + c / (x + i as f64 + 1.0)
+        });
+
+    0.5 * (2.0 * core::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// The regularized upper incomplete gamma function `Q(a, x)`.
+fn gamma_q(a: f64, x: f64) -> f64 {
+    const EPSILON: f64 = 1e-14;
+    const TINY: f64 = 1e-300;
+
+    if x <= 0.0 {
+        return 1.0;
+    }
+
+    if x.is_infinite() {
+        return 0.0;
+    }
+
+    let prefactor = (a * x.ln() - x - ln_gamma(a)).exp();
+
+    if x < a + 1.0 {
+        // Series for the lower function `P(a, x)`
+        let mut term = 1.0 / a;
+        let mut sum = term;
+
+        for n in 1..1000 {
+            term *= x / (a + n as f64);
+            sum += term;
+
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+
+        (1.0 - sum * prefactor).clamp(0.0, 1.0)
+    } else {
+        // Continued fraction for `Q(a, x)`, evaluated with Lentz's method
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / TINY;
+        let mut d = 1.0 / b;
+        let mut h = d;
+
+        for i in 1..1000 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+
+            d = an * d + b;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+
+            c = b + an / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+
+            if (delta - 1.0).abs() < EPSILON {
+                break;
+            }
+        }
+
+        (prefactor * h).clamp(0.0, 1.0)
+    }
+}
+
+#[test]
+fn test_stats() {
+    use crate::Rng;
+
+    // Known values of the distributions
+    assert!((chi_squared_p(3.841, 1) - 0.05).abs() < 1e-3);
+    assert!((chi_squared_p(18.307, 10) - 0.05).abs() < 1e-3);
+    assert!((normal_two_sided_p(1.96) - 0.05).abs() < 1e-3);
+    assert!((kolmogorov_p(1.358) - 0.05).abs() < 1e-3);
+    assert!((ln_gamma(10.0) - 362_880f64.ln()).abs() < 1e-10);
+
+    let rng = &mut Rng::from_seed(0);
+
+    for result in quality_suite(|| rng.gen_value()) {
+        assert!(result.passed(0.001), "{result:?}");
+    }
+
+    // Broken generators get caught
+    let mut counter = 0u64;
+    let counting = quality_suite(|| {
+        counter = counter.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        counter
+    });
+    assert!(counting.iter().any(|r| !r.passed(0.001)), "{counting:?}");
+
+    let weak_low_bits = quality_suite(|| rng.gen_value::<u64>() & !1);
+    assert!(!weak_low_bits[1].passed(0.001), "{weak_low_bits:?}");
+
+    let skewed = (0..10_000)
+        .map(|_| rng.gen_value::<f64>().powf(1.1))
+        .collect::<Vec<_>>();
+    assert!(!kolmogorov_smirnov(&skewed).passed(0.001));
+
+    let smoothed = (0..10_000)
+        .scan(0.5, |prev, _| {
+            *prev = (*prev + rng.gen_value::<f64>()) / 2.0;
+            Some(*prev)
+        })
+        .collect::<Vec<_>>();
+    assert!(!serial_correlation(&smoothed).passed(0.001));
+    assert!(!runs_test(&smoothed).passed(0.001));
+
+    assert!(!chi_squared(&[50, 50, 1], &[1.0, 1.0, 0.0]).passed(0.001));
+}